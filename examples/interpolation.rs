@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy::time::FixedTimestep;
+use bevy_mod_2d_hierarchy::precision::*;
+use bevy_mod_2d_hierarchy::prelude::*;
+
+const TIMESTEP_LABEL: &str = "gameplay";
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+struct FixedUpdateStage;
+
+#[derive(Component)]
+struct Velocity(RealVec2);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle2::default());
+    let transform2 = Transform2::from_xy(-300., 0.).with_scale(Transform2::IDENTITY.scale * 2.);
+    commands.spawn((
+        SpriteBundle2 {
+            texture: asset_server.load("sprite.png"),
+            transform2,
+            ..Default::default()
+        },
+        PreviousTransform2(transform2),
+        Velocity(200. * RealVec2::X),
+    ));
+}
+
+fn step(mut query: Query<(&mut Transform2, &mut Velocity)>) {
+    let dt = 0.1;
+    for (mut transform2, mut velocity) in query.iter_mut() {
+        if transform2.translation.x.abs() > 300. {
+            velocity.0 = -velocity.0;
+        }
+        transform2.translation += dt * velocity.0;
+        transform2.rotate(dt);
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(Transform2dPlugin::default())
+        .add_stage_after(
+            CoreStage::Update,
+            FixedUpdateStage,
+            SystemStage::parallel()
+                .with_run_criteria(FixedTimestep::step(0.1).with_label(TIMESTEP_LABEL))
+                .with_system(step),
+        )
+        .add_plugin(Transform2InterpolationPlugin::new(
+            FixedUpdateStage,
+            TIMESTEP_LABEL,
+        ))
+        .add_startup_system(setup)
+        .run();
+}
@@ -7,7 +7,8 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn(SpriteBundle2 {
             texture: texture.clone(),
-            transform2: Transform2::from_rotation(0.5 * std::f32::consts::PI).with_scale(3.),
+            transform2: Transform2::from_rotation(Rotation2::degrees(90.))
+                .with_scale(Transform2::IDENTITY.scale * 3.),
             ..Default::default()
         })
         .with_children(|builder| {
@@ -27,7 +28,7 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugin(Transform2dPlugin)
+        .add_plugin(Transform2dPlugin::default())
         .add_startup_system(spawn)
         .run();
 }
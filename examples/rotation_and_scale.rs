@@ -1,6 +1,6 @@
 use bevy::prelude::*;
+use bevy_mod_2d_hierarchy::precision::*;
 use bevy_mod_2d_hierarchy::prelude::*;
-use std::f32::consts::PI;
 
 #[derive(Component)]
 pub struct Center;
@@ -14,15 +14,15 @@ pub struct White;
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(Camera2dBundle::default());
     let s = 64f32;
-    let d = 3. * s * Vec2::X;
+    let d = 3. * Real::from(s) * RealVec2::X;
     let n = 7;
     let center_id = commands
         .spawn(SpatialBundle2::default())
         .insert(Center)
         .id();
     for i in 0..n {
-        let angle = i as f32 * (n as f32).recip() * PI;
-        let translation = Mat2::from_angle(angle) * d * (1. - 2. * (i % 2) as f32);
+        let angle = Rotation2::degrees(180. * i as Real / n as Real);
+        let translation = angle.to_mat2() * d * (1. - 2. * (i % 2) as Real);
         let sprite_id = commands
             .spawn(SpriteBundle2 {
                 sprite: Sprite {
@@ -31,7 +31,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     ..Default::default()
                 },
                 texture: asset_server.load("sprite.png"),
-                transform2: Transform2::from_translation(-translation).with_depth(i as f32),
+                transform2: Transform2::from_translation(-translation).with_depth(i as Real),
                 ..Default::default()
             })
             .insert(White)
@@ -44,7 +44,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     ..Default::default()
                 },
                 texture: asset_server.load("sprite.png"),
-                transform2: Transform2::from_translation(translation).with_depth(i as f32),
+                transform2: Transform2::from_translation(translation).with_depth(i as Real),
                 propagate: PropagateTransform2::TRANSLATION,
                 ..Default::default()
             })
@@ -56,24 +56,27 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn update(
     time: Res<Time>,
     mut point_query: Query<&mut Transform2, (With<Center>, Without<Red>, Without<White>)>,
     mut red_query: Query<&mut Transform2, With<Red>>,
 ) {
     point_query.for_each_mut(|mut transform| {
-        transform.rotate(0.3 * time.delta_seconds());
-        transform.scale = 1. + time.elapsed_seconds().sin();
+        transform.rotate(0.3 * time.delta_seconds_f64() as Real);
+        transform.scale =
+            Transform2::IDENTITY.scale * (1. + time.elapsed_seconds_f64().sin() as Real);
     });
     red_query.for_each_mut(|mut transform| {
-        transform.rotation = 0.2 * (2.5 * time.elapsed_seconds()).sin();
+        transform.rotation =
+            Rotation2::radians(0.2 * (2.5 * time.elapsed_seconds_f64()).sin() as Real);
     });
 }
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
-        .add_plugin(Transform2dPlugin)
+        .add_plugin(Transform2dPlugin::default())
         .add_startup_system(setup)
         .add_system(update)
         .run();
@@ -1,9 +1,9 @@
-use std::f32::consts::PI;
-
 use bevy::math::vec2;
 use bevy::math::vec3;
 use bevy::prelude::*;
+use bevy_mod_2d_hierarchy::precision::*;
 use bevy_mod_2d_hierarchy::prelude::*;
+use std::f32::consts::PI;
 
 pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(Camera2dBundle2::default());
@@ -28,8 +28,8 @@ pub fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             builder.spawn((
                 sprite.clone(),
                 Transform2 {
-                    rotation: PI / 4.,
-                    scale: 2.0,
+                    rotation: Rotation2::degrees(45.),
+                    scale: Transform2::IDENTITY.scale * 2.,
                     ..Default::default()
                 },
                 image_handle.clone(),
@@ -71,6 +71,7 @@ pub fn rotation(
     tf.for_each_mut(|mut tf| {
         tf.rotate_z(angle);
     });
+    let angle = 0.5 * time.delta_seconds_f64() as Real;
     tf2d.for_each_mut(|mut tf| {
         tf.rotate(angle);
     });
@@ -79,7 +80,7 @@ pub fn rotation(
 pub fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugin(Transform2dPlugin)
+        .add_plugin(Transform2dPlugin::default())
         .add_startup_system(setup)
         .add_system(rotation)
         .run();
@@ -1,6 +1,7 @@
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::diagnostic::LogDiagnosticsPlugin;
 use bevy::prelude::*;
+use bevy_mod_2d_hierarchy::precision::*;
 use bevy_mod_2d_hierarchy::prelude::*;
 
 pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -11,7 +12,7 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
             commands
                 .spawn(SpriteBundle2 {
                     texture: texture.clone(),
-                    transform2: Transform2::from_xy(x as f32 * 16., y as f32 * 16.),
+                    transform2: Transform2::from_xy(x as Real * 16., y as Real * 16.),
                     ..Default::default()
                 })
                 .with_children(|builder| {
@@ -30,11 +31,11 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 pub fn update(time: Res<Time>, mut query: Query<&mut Transform2>) {
-    query.for_each_mut(|mut transform| transform.rotate(time.delta_seconds()));
+    query.for_each_mut(|mut transform| transform.rotate(time.delta_seconds_f64() as Real));
 }
 
 pub fn update_2(time: Res<Time>, mut query: Query<&mut Transform2, With<Parent>>) {
-    query.for_each_mut(|mut transform| transform.rotate(time.delta_seconds()));
+    query.for_each_mut(|mut transform| transform.rotate(time.delta_seconds_f64() as Real));
 }
 
 fn main() {
@@ -46,9 +47,9 @@ fn main() {
             },
             ..Default::default()
         }))
-        .add_plugin(Transform2dPlugin)
+        .add_plugin(Transform2dPlugin::default())
         .add_plugin(LogDiagnosticsPlugin::default())
-        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(FrameTimeDiagnosticsPlugin)
         .add_startup_system(spawn)
         .add_system(update)
         .add_system(update_2)
@@ -50,7 +50,7 @@ fn main() {
             ..Default::default()
         }))
         .add_plugin(LogDiagnosticsPlugin::default())
-        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(FrameTimeDiagnosticsPlugin)
         .add_startup_system(spawn)
         .add_system(update)
         .add_system(update_2)
@@ -0,0 +1,249 @@
+use crate::helper::TransformHelper2;
+use crate::interpolation::PreviousTransform2;
+use crate::pivot::Pivot2;
+use crate::prelude::*;
+use bevy::ecs::system::Command;
+use bevy::ecs::system::EntityCommands;
+use bevy::ecs::system::SystemState;
+use bevy::hierarchy::AddChild;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Command that changes the parent of an entity without moving it in world space.
+///
+/// The local [`Transform2`] of the child is recomputed so that its [`GlobalTransform2`] stays
+/// the same under the new parent, honoring its [`PropagateTransform2`] or the
+/// [`InheritedPropagation2`] it inherits from its new ancestors, along with its [`Pivot2`] and
+/// [`PropagationWeights2`]. With `parent: None`, the child becomes a root.
+///
+/// If the world transform can't be kept, because it can't be computed or the new parent has a
+/// zero scale the child inherits, a warning is logged and the hierarchy is left unchanged. An
+/// entity can't be made its own parent.
+#[derive(Debug)]
+pub struct SetParentInPlace2 {
+    pub child: Entity,
+    pub parent: Option<Entity>,
+}
+
+impl Command for SetParentInPlace2 {
+    fn write(self, world: &mut World) {
+        if self.parent == Some(self.child) {
+            warn!("{:?} can't be its own parent", self.child);
+            return;
+        }
+        let local = match reparented_local_transform(world, self.child, self.parent) {
+            Ok(local) => local,
+            Err(reason) => {
+                warn!(
+                    "{:?} isn't reparented to {:?}, {reason}",
+                    self.child, self.parent
+                );
+                return;
+            }
+        };
+        match self.parent {
+            Some(parent) => AddChild {
+                parent,
+                child: self.child,
+            }
+            .write(world),
+            None => {
+                if let Some(previous_parent) = world.get::<Parent>(self.child).map(Parent::get) {
+                    world
+                        .entity_mut(previous_parent)
+                        .remove_children(&[self.child]);
+                }
+            }
+        }
+        let mut entity = world.entity_mut(self.child);
+        if let Some(mut transform2) = entity.get_mut::<Transform2>() {
+            *transform2 = local;
+        }
+        // the previous transform is relative to the old parent, don't interpolate from it
+        if let Some(mut previous) = entity.get_mut::<PreviousTransform2>() {
+            previous.0 = local;
+        }
+    }
+}
+
+/// The local [`Transform2`] that keeps `child` in place under `parent`, or why there isn't one.
+fn reparented_local_transform(
+    world: &mut World,
+    child: Entity,
+    parent: Option<Entity>,
+) -> Result<Transform2, String> {
+    let mut state = SystemState::<TransformHelper2>::new(world);
+    let helper = state.get(world);
+    let global_transform2 = helper
+        .compute_global_transform(child)
+        .map_err(|error| error.to_string())?;
+    let propagation = match parent {
+        Some(parent) => world
+            .get::<PropagateTransform2>(child)
+            .copied()
+            .unwrap_or_else(|| inherited_propagation(world, parent)),
+        None => PropagateTransform2::NOTHING,
+    };
+    let mut parent_global_transform2 = match parent {
+        Some(parent) => helper
+            .compute_global_transform(parent)
+            .map_err(|error| error.to_string())?,
+        None => GlobalTransform2::IDENTITY,
+    };
+    if let Some(weights) = world.get::<PropagationWeights2>(child) {
+        parent_global_transform2 = parent_global_transform2.weighted(weights);
+    }
+    let local = global_transform2
+        .reparented_to(&parent_global_transform2, propagation)
+        .ok_or_else(|| "its world transform can't be kept under the new parent".to_string())?;
+    Ok(match world.get::<Pivot2>(child) {
+        Some(pivot) => pivot.unapply(local),
+        None => local,
+    })
+}
+
+/// The [`InheritedPropagation2`] of `entity` or its nearest ancestor that has one.
+fn inherited_propagation(world: &World, entity: Entity) -> PropagateTransform2 {
+    let mut visited = HashSet::new();
+    let mut ancestor = entity;
+    loop {
+        if let Some(inherited) = world.get::<InheritedPropagation2>(ancestor) {
+            return inherited.0;
+        }
+        match world.get::<Parent>(ancestor) {
+            Some(parent) if visited.insert(ancestor) => ancestor = parent.get(),
+            _ => return PropagateTransform2::ALL,
+        }
+    }
+}
+
+/// Extension methods for [`EntityCommands`] that reparent 2D entities without moving them.
+pub trait BuildChildrenInPlace2 {
+    /// Makes this entity a child of `parent`, keeping its [`GlobalTransform2`], see [`SetParentInPlace2`].
+    fn set_parent_in_place_2d(&mut self, parent: Entity) -> &mut Self;
+
+    /// Makes this entity a root, keeping its [`GlobalTransform2`], see [`SetParentInPlace2`].
+    fn remove_parent_in_place_2d(&mut self) -> &mut Self;
+}
+
+impl<'w, 's, 'a> BuildChildrenInPlace2 for EntityCommands<'w, 's, 'a> {
+    fn set_parent_in_place_2d(&mut self, parent: Entity) -> &mut Self {
+        let child = self.id();
+        self.commands().add(SetParentInPlace2 {
+            child,
+            parent: Some(parent),
+        });
+        self
+    }
+
+    fn remove_parent_in_place_2d(&mut self) -> &mut Self {
+        let child = self.id();
+        self.commands().add(SetParentInPlace2 {
+            child,
+            parent: None,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::precision::*;
+    use bevy::ecs::system::CommandQueue;
+    use bevy::transform::TransformPlugin;
+
+    fn assert_near(a: &GlobalTransform2, b: &GlobalTransform2) {
+        assert!(a.affine().abs_diff_eq(b.affine(), 0.001), "{a} != {b}");
+        assert!((a.depth() - b.depth()).abs() < 0.001, "{a} != {b}");
+    }
+
+    fn global_transform2(app: &App, entity: Entity) -> GlobalTransform2 {
+        *app.world.get::<GlobalTransform2>(entity).unwrap()
+    }
+
+    fn apply(app: &mut App, command: impl FnOnce(&mut Commands)) {
+        let mut queue = CommandQueue::default();
+        command(&mut Commands::new(&mut queue, &app.world));
+        queue.apply(&mut app.world);
+        app.update();
+    }
+
+    #[test]
+    fn reparent_in_place() {
+        let mut app = App::new();
+        app.add_plugin(TransformPlugin)
+            .add_plugin(Transform2dPlugin::default());
+        let parent = app
+            .world
+            .spawn(TransformBundle2::from(
+                Transform2::from_xyz(10., 5., 1.)
+                    .with_rotation(Rotation2::degrees(30.))
+                    .with_scale(Transform2::IDENTITY.scale * 2.),
+            ))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle2::from(
+                Transform2::from_xyz(-3., 4., 2.).with_rotation(Rotation2::degrees(-60.)),
+            ))
+            .insert(Pivot2::new(1., 1.))
+            .id();
+        app.update();
+        let before = global_transform2(&app, child);
+
+        apply(&mut app, |commands| {
+            commands.entity(child).set_parent_in_place_2d(parent);
+        });
+        assert_eq!(app.world.get::<Parent>(child).unwrap().get(), parent);
+        assert_near(&global_transform2(&app, child), &before);
+
+        // move the parent, then detach the child where it ended up
+        app.world.get_mut::<Transform2>(parent).unwrap().translation += RealVec2::new(3., 0.);
+        app.update();
+        let moved = global_transform2(&app, child);
+        apply(&mut app, |commands| {
+            commands.entity(child).remove_parent_in_place_2d();
+        });
+        assert!(app.world.get::<Parent>(child).is_none());
+        assert_near(&global_transform2(&app, child), &moved);
+
+        app.world
+            .entity_mut(child)
+            .insert(PropagateTransform2::TRANSLATION);
+        app.update();
+        apply(&mut app, |commands| {
+            commands.entity(child).set_parent_in_place_2d(parent);
+        });
+        assert_near(&global_transform2(&app, child), &moved);
+
+        // refused, the hierarchy is left as it is
+        apply(&mut app, |commands| {
+            commands.entity(child).set_parent_in_place_2d(child);
+        });
+        assert_eq!(app.world.get::<Parent>(child).unwrap().get(), parent);
+        let flat = app
+            .world
+            .spawn(TransformBundle2::from(
+                Transform2::IDENTITY.with_scale(Transform2::IDENTITY.scale * 0.),
+            ))
+            .id();
+        apply(&mut app, |commands| {
+            commands.entity(child).set_parent_in_place_2d(flat);
+        });
+        assert_eq!(app.world.get::<Parent>(child).unwrap().get(), parent);
+        assert_near(&global_transform2(&app, child), &moved);
+
+        // onto the `Transform` side of a mixed hierarchy
+        let parent_3d = app
+            .world
+            .spawn(TransformBundle::from(Transform::from_xyz(5., 0., 0.)))
+            .id();
+        app.update();
+        apply(&mut app, |commands| {
+            commands.entity(child).set_parent_in_place_2d(parent_3d);
+        });
+        assert_eq!(app.world.get::<Parent>(child).unwrap().get(), parent_3d);
+        assert_near(&global_transform2(&app, child), &moved);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::interpolation::PreviousTransform2;
+use crate::precision::*;
+use crate::prelude::*;
+use crate::Transform2dStage;
+use crate::Transform2dSystem;
+use bevy::prelude::*;
+
+/// Settings for the floating origin, see [`FloatingOriginPlugin`].
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct FloatingOrigin2 {
+    /// How far the active camera can move from the origin before the world is shifted back.
+    pub threshold: Real,
+}
+
+/// Sent when the world is shifted to bring the active camera back to the origin.
+///
+/// `offset` has been subtracted from the translation of every root [`Transform2`],
+/// positions cached by gameplay systems should be corrected by the same amount.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OriginShifted2 {
+    pub offset: RealVec2,
+}
+
+/// Keeps the active 2D camera near the world origin by shifting every root [`Transform2`]
+/// once the camera is more than `threshold` away from it.
+///
+/// Only entities without a [`Parent`] are moved, their descendants follow through propagation.
+/// The system runs in the [`Transform2dStage`], so add this plugin after [`Transform2dPlugin`].
+pub struct FloatingOriginPlugin {
+    pub threshold: Real,
+}
+
+impl Plugin for FloatingOriginPlugin {
+    fn build(&self, app: &mut App) {
+        let stage = Transform2dStage::of(app);
+        app.insert_resource(FloatingOrigin2 {
+            threshold: self.threshold,
+        })
+        .add_event::<OriginShifted2>()
+        .add_system_to_stage(
+            stage,
+            floating_origin_system
+                .label(Transform2dSystem::FloatingOriginSystem)
+                .before(Transform2dSystem::PropagateTransform2System),
+        );
+    }
+}
+
+/// Shifts the root entities when the active 2D camera is further than
+/// [`FloatingOrigin2::threshold`] from the origin.
+#[allow(clippy::type_complexity)]
+pub fn floating_origin_system(
+    floating_origin: Res<FloatingOrigin2>,
+    camera_query: Query<(Entity, &Camera, &GlobalTransform2), With<Camera2d>>,
+    mut root_query: Query<(&mut Transform2, Option<&mut PreviousTransform2>), Without<Parent>>,
+    mut origin_shifted_events: EventWriter<OriginShifted2>,
+) {
+    let Some((camera_entity, _, camera_global_transform2)) =
+        camera_query.iter().find(|(_, camera, _)| camera.is_active)
+    else {
+        return;
+    };
+
+    // a root camera may have moved since its global transform was last propagated
+    let offset = root_query
+        .get(camera_entity)
+        .map_or(camera_global_transform2.translation(), |(transform2, _)| {
+            transform2.translation
+        });
+    if offset.length() <= floating_origin.threshold {
+        return;
+    }
+
+    root_query.for_each_mut(|(mut transform2, previous)| {
+        transform2.translation -= offset;
+        if let Some(mut previous) = previous {
+            previous.0.translation -= offset;
+        }
+    });
+    origin_shifted_events.send(OriginShifted2 { offset });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy::ecs::event::ManualEventReader;
+
+    fn translation(app: &App, entity: Entity) -> RealVec2 {
+        app.world.get::<Transform2>(entity).unwrap().translation
+    }
+
+    #[test]
+    fn shift_origin() {
+        let mut app = App::new();
+        app.add_plugin(Transform2dPlugin::default())
+            .add_plugin(FloatingOriginPlugin { threshold: 100. });
+        let camera = app.world.spawn(Camera2dBundle2::default()).id();
+        let root = app
+            .world
+            .spawn((
+                TransformBundle2::from(Transform2::from_xy(60., 10.)),
+                PreviousTransform2(Transform2::from_xy(40., 10.)),
+            ))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(5., 0.)))
+            .id();
+        app.world.entity_mut(root).push_children(&[child]);
+        app.world.get_mut::<Transform2>(camera).unwrap().translation = RealVec2::new(50., 0.);
+        let mut reader = ManualEventReader::<OriginShifted2>::default();
+
+        // below the threshold nothing moves
+        app.update();
+        assert_eq!(translation(&app, camera), RealVec2::new(50., 0.));
+        assert_eq!(translation(&app, root), RealVec2::new(60., 10.));
+        let events = app.world.resource::<Events<OriginShifted2>>();
+        assert_eq!(reader.iter(events).count(), 0);
+
+        app.world.get_mut::<Transform2>(camera).unwrap().translation = RealVec2::new(150., 0.);
+        app.update();
+        let offset = RealVec2::new(150., 0.);
+        assert_eq!(translation(&app, camera), RealVec2::ZERO);
+        assert_eq!(translation(&app, root), RealVec2::new(-90., 10.));
+        assert_eq!(
+            app.world
+                .get::<PreviousTransform2>(root)
+                .unwrap()
+                .0
+                .translation,
+            RealVec2::new(-110., 10.)
+        );
+        // descendants follow their root
+        assert_eq!(translation(&app, child), RealVec2::new(5., 0.));
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform2>(child)
+                .unwrap()
+                .translation(),
+            RealVec2::new(-85., 10.)
+        );
+        let events = app.world.resource::<Events<OriginShifted2>>();
+        let sent: Vec<_> = reader.iter(events).copied().collect();
+        assert_eq!(sent, vec![OriginShifted2 { offset }]);
+
+        app.update();
+        assert_eq!(translation(&app, root), RealVec2::new(-90., 10.));
+        let events = app.world.resource::<Events<OriginShifted2>>();
+        assert_eq!(reader.iter(events).count(), 0);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::mixed::MixedParent;
+use crate::pivot::Pivot2;
+use crate::prelude::*;
+use crate::systems::local_transform;
+use crate::systems::propagate_child;
+use crate::systems::RenderOrigin2;
+use bevy::ecs::query::QueryEntityError;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Why [`TransformHelper2`] couldn't compute a [`GlobalTransform2`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComputeGlobalTransform2Error {
+    /// The entity or one of its ancestors has neither a [`Transform2`] nor a [`Transform`].
+    MissingTransform2(Entity),
+    /// The entity or one of its ancestors doesn't exist, or is its own ancestor.
+    MalformedHierarchy(Entity),
+}
+
+impl std::fmt::Display for ComputeGlobalTransform2Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingTransform2(entity) => {
+                write!(f, "{entity:?} doesn't have a Transform2 or a Transform")
+            }
+            Self::MalformedHierarchy(entity) => write!(
+                f,
+                "Malformed hierarchy, {entity:?} doesn't exist or is its own ancestor"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ComputeGlobalTransform2Error {}
+
+/// Computes the current [`GlobalTransform2`] of an entity, without waiting for propagation.
+///
+/// The [`Transform2`] of the entity and each of its ancestors are combined like the propagation
+/// systems do, with their [`PropagateTransform2`], [`InheritedPropagation2`], [`Pivot2`] and
+/// [`PropagationWeights2`], so changes made earlier in the frame are included. [`Transform`]
+/// ancestors in a mixed hierarchy are combined like
+/// [`propagate_mixed_hierarchies_system`](crate::mixed::propagate_mixed_hierarchies_system) does.
+/// This walks the whole ancestor chain on every call, prefer [`GlobalTransform2`] when a frame
+/// old value is fine.
+///
+/// The global transform of a [`Transform`] entity is its render transform projected onto the
+/// XY plane, see [`GlobalTransform2::from_render_transform`].
+#[derive(SystemParam)]
+pub struct TransformHelper2<'w, 's> {
+    render_origin: Res<'w, RenderOrigin2>,
+    parent_query: Query<'w, 's, &'static Parent>,
+    #[allow(clippy::type_complexity)]
+    transform_query: Query<
+        'w,
+        's,
+        (
+            &'static Transform2,
+            Option<&'static Pivot2>,
+            Option<&'static PropagationWeights2>,
+            Option<&'static PropagateTransform2>,
+        ),
+    >,
+    transform_3d_query: Query<'w, 's, &'static Transform>,
+    inherited_query: Query<'w, 's, &'static InheritedPropagation2>,
+}
+
+impl<'w, 's> TransformHelper2<'w, 's> {
+    /// Computes the [`GlobalTransform2`] of `entity` from its [`Transform2`] and those of its ancestors.
+    pub fn compute_global_transform(
+        &self,
+        entity: Entity,
+    ) -> Result<GlobalTransform2, ComputeGlobalTransform2Error> {
+        let mut ancestors = vec![entity];
+        let mut visited = HashSet::new();
+        visited.insert(entity);
+        let mut ancestor = entity;
+        while let Ok(parent) = self.parent_query.get(ancestor) {
+            ancestor = parent.get();
+            if !visited.insert(ancestor) {
+                return Err(ComputeGlobalTransform2Error::MalformedHierarchy(ancestor));
+            }
+            ancestors.push(ancestor);
+        }
+
+        let origin = self.render_origin.0;
+        let mut global = None;
+        let mut inherited = PropagateTransform2::ALL;
+        for &ancestor in ancestors.iter().rev() {
+            global = Some(match self.transform_query.get(ancestor) {
+                Ok((transform2, pivot, weights, propagate)) => {
+                    let local = local_transform(transform2, pivot);
+                    let propagate = propagate.copied().unwrap_or(inherited);
+                    MixedParent::Global2(match global {
+                        Some(MixedParent::Global2(parent)) => {
+                            propagate_child(&parent, local, propagate, weights)
+                        }
+                        Some(MixedParent::Global(parent)) => propagate_child(
+                            &GlobalTransform2::from_render_transform(&parent, origin),
+                            local,
+                            propagate,
+                            weights,
+                        ),
+                        None => local.into(),
+                    })
+                }
+                Err(QueryEntityError::NoSuchEntity(_)) => {
+                    return Err(ComputeGlobalTransform2Error::MalformedHierarchy(ancestor));
+                }
+                Err(_) => {
+                    let transform = self
+                        .transform_3d_query
+                        .get(ancestor)
+                        .map_err(|_| ComputeGlobalTransform2Error::MissingTransform2(ancestor))?;
+                    MixedParent::Global(match global {
+                        Some(MixedParent::Global2(parent)) => {
+                            parent.to_render_transform(origin).mul_transform(*transform)
+                        }
+                        Some(MixedParent::Global(parent)) => parent.mul_transform(*transform),
+                        None => GlobalTransform::from(*transform),
+                    })
+                }
+            });
+            inherited = self.inherited(ancestor, inherited);
+        }
+        Ok(match global {
+            Some(MixedParent::Global2(global_transform2)) => global_transform2,
+            Some(MixedParent::Global(global_transform)) => {
+                GlobalTransform2::from_render_transform(&global_transform, origin)
+            }
+            None => GlobalTransform2::default(),
+        })
+    }
+
+    /// The default propagation the descendants of `entity` inherit.
+    fn inherited(&self, entity: Entity, inherited: PropagateTransform2) -> PropagateTransform2 {
+        self.inherited_query
+            .get(entity)
+            .map_or(inherited, |entity_inherited| entity_inherited.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::precision::*;
+    use crate::test_util::assert_near;
+    use bevy::ecs::system::SystemState;
+
+    #[test]
+    fn compute_global_transform() {
+        let mut app = App::new();
+        app.add_plugin(Transform2dPlugin::default());
+        let parent = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(10., 0.)))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(1., 0.)))
+            .insert(PropagateTransform2::TRANSLATION)
+            .id();
+        app.world.entity_mut(parent).push_children(&[child]);
+        app.update();
+
+        // changed after propagation
+        app.world.get_mut::<Transform2>(parent).unwrap().rotation = Rotation2::degrees(90.);
+        app.world
+            .get_mut::<Transform2>(child)
+            .unwrap()
+            .translation
+            .y = 2.;
+        let mut state = SystemState::<TransformHelper2>::new(&mut app.world);
+        let helper = state.get(&app.world);
+        let computed = helper.compute_global_transform(child).unwrap();
+        // the offset is rotated by the parent, but the child doesn't inherit the rotation
+        assert_near(computed.translation(), RealVec2::new(8., 1.));
+        assert!(computed.rotation().as_radians().abs() < 0.001);
+
+        app.update();
+        let propagated = app.world.get::<GlobalTransform2>(child).unwrap();
+        assert!(propagated.affine().abs_diff_eq(computed.affine(), 0.001));
+
+        // a mixed hierarchy continues through the `Transform` ancestor
+        let orphan = app.world.spawn(Transform2::from_xy(1., 0.)).id();
+        let parent_3d = app.world.spawn(Transform::from_xyz(5., 0., 0.)).id();
+        app.world.entity_mut(parent_3d).push_children(&[orphan]);
+        let helper = state.get(&app.world);
+        let computed = helper.compute_global_transform(orphan).unwrap();
+        assert_near(computed.translation(), RealVec2::new(6., 0.));
+
+        let empty = app.world.spawn_empty().id();
+        app.world.entity_mut(empty).push_children(&[parent_3d]);
+        let helper = state.get(&app.world);
+        assert_eq!(
+            helper.compute_global_transform(orphan),
+            Err(ComputeGlobalTransform2Error::MissingTransform2(empty))
+        );
+
+        // despawning without updating the hierarchy leaves a dangling `Parent`
+        app.world.despawn(empty);
+        let helper = state.get(&app.world);
+        assert_eq!(
+            helper.compute_global_transform(orphan),
+            Err(ComputeGlobalTransform2Error::MalformedHierarchy(empty))
+        );
+    }
+}
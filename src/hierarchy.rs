@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// A malformed hierarchy found while propagating transforms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HierarchyError2 {
+    /// `entity` is listed in the [`Children`] of `expected_parent`, but its [`Parent`] is `actual_parent`.
+    ParentMismatch {
+        entity: Entity,
+        expected_parent: Entity,
+        actual_parent: Entity,
+    },
+    /// `entity` is listed in the [`Children`] of `parent`, but doesn't exist.
+    MissingEntity { entity: Entity, parent: Entity },
+    /// `entity` is its own ancestor, so it and its descendants are never propagated.
+    Cycle { entity: Entity },
+}
+
+impl std::fmt::Display for HierarchyError2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParentMismatch {
+                entity,
+                expected_parent,
+                actual_parent,
+            } => write!(
+                f,
+                "Malformed hierarchy, {entity:?} is a child of {expected_parent:?} but its parent is {actual_parent:?}"
+            ),
+            Self::MissingEntity { entity, parent } => write!(
+                f,
+                "Malformed hierarchy, {entity:?} is a child of {parent:?} but doesn't exist"
+            ),
+            Self::Cycle { entity } => {
+                write!(f, "Malformed hierarchy, {entity:?} is its own ancestor")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError2 {}
+
+/// What the propagation systems do when they find a [`HierarchyError2`].
+///
+/// Propagation always skips the affected subtree and carries on with the rest of the hierarchy,
+/// unless the policy is [`HierarchyErrorPolicy2::Panic`].
+///
+/// Defaults to `LogOnce`, since a child despawned with `despawn` rather than `despawn_recursive`
+/// stays listed in its parent's [`Children`] and is reported as a [`HierarchyError2::MissingEntity`].
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HierarchyErrorPolicy2 {
+    /// Panics on the first [`HierarchyError2`], useful to catch hierarchy bugs in tests.
+    Panic,
+    /// Logs an error the first time each distinct [`HierarchyError2`] is found.
+    #[default]
+    LogOnce,
+    /// Sends each [`HierarchyError2`] as an event, every frame it's found.
+    Event,
+}
+
+/// Handles `errors` according to the `policy`.
+///
+/// `reported` holds the errors that have already been logged by the calling system.
+pub(crate) fn report_hierarchy_errors(
+    errors: impl IntoIterator<Item = HierarchyError2>,
+    policy: HierarchyErrorPolicy2,
+    reported: &mut HashSet<HierarchyError2>,
+    events: &mut EventWriter<HierarchyError2>,
+) {
+    for error in errors {
+        match policy {
+            HierarchyErrorPolicy2::Panic => panic!("{error}"),
+            HierarchyErrorPolicy2::LogOnce => {
+                if reported.insert(error) {
+                    error!("{error}");
+                }
+            }
+            HierarchyErrorPolicy2::Event => events.send(error),
+        }
+    }
+}
+
+/// Reports entities with a changed [`Parent`] that have become their own ancestor.
+pub fn detect_hierarchy_cycles_system(
+    policy: Res<HierarchyErrorPolicy2>,
+    mut reported: Local<HashSet<HierarchyError2>>,
+    mut events: EventWriter<HierarchyError2>,
+    changed_query: Query<Entity, Changed<Parent>>,
+    parent_query: Query<&Parent>,
+) {
+    let mut visited = HashSet::new();
+    let cycles = changed_query.iter().filter(|&entity| {
+        visited.clear();
+        let mut ancestor = entity;
+        while let Ok(parent) = parent_query.get(ancestor) {
+            ancestor = parent.get();
+            if ancestor == entity {
+                return true;
+            }
+            // a cycle further up that doesn't include `entity`
+            if !visited.insert(ancestor) {
+                return false;
+            }
+        }
+        false
+    });
+    report_hierarchy_errors(
+        cycles.map(|entity| HierarchyError2::Cycle { entity }),
+        *policy,
+        &mut reported,
+        &mut events,
+    );
+}
@@ -0,0 +1,332 @@
+use crate::pivot::Pivot2;
+use crate::precision::*;
+use crate::prelude::*;
+use crate::systems::local_transform;
+use crate::systems::propagate_child;
+use crate::transform2::InheritedPropagation2;
+use crate::transform2::PropagationWeights2;
+use crate::Transform2dStage;
+use crate::Transform2dSystem;
+use bevy::ecs::schedule::StageLabelId;
+use bevy::prelude::*;
+use bevy::time::FixedTimesteps;
+use bevy::utils::HashSet;
+
+/// The [`Transform2`] of an entity at the start of the latest fixed timestep.
+///
+/// Entities with this component are rendered at a blend of their previous and current
+/// [`Transform2`], weighted by how far the current frame is into the next fixed timestep.
+/// Set it to the current transform to teleport an entity without interpolating.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct PreviousTransform2(pub Transform2);
+
+impl From<Transform2> for PreviousTransform2 {
+    #[inline]
+    fn from(transform2: Transform2) -> Self {
+        Self(transform2)
+    }
+}
+
+/// The label of the [`FixedTimestep`](bevy::time::FixedTimestep) run criteria that
+/// [`Transform2InterpolationPlugin`] reads the overstep fraction from.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct InterpolationTimestep2(pub &'static str);
+
+/// Interpolates the rendered transforms of entities whose [`Transform2`] is updated on a fixed timestep.
+///
+/// Only [`GlobalTransform2`] and [`GlobalTransform`] of entities with a [`PreviousTransform2`]
+/// and their descendants receive the blended transform, [`Transform2`] is left untouched.
+///
+/// The fixed stage must already be added to the app when this plugin is built, and the
+/// interpolation runs in the [`Transform2dStage`], so add this plugin after [`Transform2dPlugin`].
+pub struct Transform2InterpolationPlugin {
+    fixed_stage: StageLabelId,
+    timestep_label: &'static str,
+}
+
+impl Transform2InterpolationPlugin {
+    /// `fixed_stage` is the stage running the gameplay systems, and `timestep_label`
+    /// the label given to its [`FixedTimestep`](bevy::time::FixedTimestep) with `with_label`.
+    pub fn new(fixed_stage: impl StageLabel, timestep_label: &'static str) -> Self {
+        Self {
+            fixed_stage: fixed_stage.as_label(),
+            timestep_label,
+        }
+    }
+}
+
+impl Plugin for Transform2InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        let stage = Transform2dStage::of(app);
+        let interpolate_transform2 = interpolate_transform2_system
+            .label(Transform2dSystem::InterpolateTransform2System)
+            .after(Transform2dSystem::PropagateTransform2System)
+            .before(Transform2dSystem::PropagateMixedHierarchiesSystem);
+        // the render origin follows the camera's interpolated transform
+        #[cfg(feature = "f64")]
+        let interpolate_transform2 =
+            interpolate_transform2.before(Transform2dSystem::UpdateRenderOriginSystem);
+
+        app.register_type::<PreviousTransform2>()
+            .insert_resource(InterpolationTimestep2(self.timestep_label))
+            .add_system_to_stage(
+                self.fixed_stage,
+                update_previous_transform2_system.at_start(),
+            )
+            .add_system_to_stage(
+                stage,
+                initialize_previous_transform2_system
+                    .before(Transform2dSystem::InterpolateTransform2System),
+            )
+            .add_system_to_stage(stage, interpolate_transform2);
+    }
+}
+
+/// Stores the [`Transform2`] of interpolated entities before each fixed timestep runs.
+pub fn update_previous_transform2_system(mut query: Query<(&Transform2, &mut PreviousTransform2)>) {
+    query.for_each_mut(|(transform2, mut previous)| {
+        previous.0 = *transform2;
+    });
+}
+
+/// Initializes newly added [`PreviousTransform2`] components, entities that haven't
+/// seen a fixed timestep yet would otherwise blend from the identity transform.
+pub fn initialize_previous_transform2_system(
+    mut query: Query<(&Transform2, &mut PreviousTransform2), Added<PreviousTransform2>>,
+) {
+    query.for_each_mut(|(transform2, mut previous)| {
+        previous.0 = *transform2;
+    });
+}
+
+/// Writes the blend of [`PreviousTransform2`] and [`Transform2`] into the [`GlobalTransform2`]
+/// of interpolated entities, then propagates it to their descendants.
+#[allow(clippy::type_complexity)]
+pub fn interpolate_transform2_system(
+    timestep: Res<InterpolationTimestep2>,
+    fixed_timesteps: Res<FixedTimesteps>,
+    interpolated_query: Query<(Entity, Option<&Parent>), With<PreviousTransform2>>,
+    ancestor_query: Query<(
+        Option<&Parent>,
+        Option<&PreviousTransform2>,
+        Option<&InheritedPropagation2>,
+    )>,
+    inherited_query: Query<&InheritedPropagation2>,
+    mut transform_query: Query<(
+        &Transform2,
+        Option<&PreviousTransform2>,
+        Option<&Pivot2>,
+        Option<&PropagationWeights2>,
+        &mut GlobalTransform2,
+        Option<&PropagateTransform2>,
+        Option<&Parent>,
+    )>,
+    children_query: Query<&Children>,
+) {
+    let s = fixed_timesteps
+        .get(timestep.0)
+        .map_or(1., |state| real_from_f64(state.overstep_percentage()));
+
+    let mut visited = HashSet::new();
+    for (entity, parent) in interpolated_query.iter() {
+        let Some(parent) = parent else {
+            interpolate_recursive(
+                None,
+                &mut transform_query,
+                &children_query,
+                &inherited_query,
+                entity,
+                None,
+                PropagateTransform2::ALL,
+                s,
+            );
+            continue;
+        };
+
+        // subtrees of interpolated ancestors are handled when their ancestor is visited
+        let mut ancestor = parent.get();
+        let mut has_interpolated_ancestor = false;
+        let mut inherited = None;
+        visited.clear();
+        while let Ok((next, previous, ancestor_inherited)) = ancestor_query.get(ancestor) {
+            // entities in or below a cycle aren't propagated, see `HierarchyError2::Cycle`
+            if previous.is_some() || !visited.insert(ancestor) {
+                has_interpolated_ancestor = true;
+                break;
+            }
+            if inherited.is_none() {
+                inherited = ancestor_inherited.map(|inherited| inherited.0);
+            }
+            match next {
+                Some(next) => ancestor = next.get(),
+                None => break,
+            }
+        }
+        if has_interpolated_ancestor {
+            continue;
+        }
+
+        if let Ok((_, _, _, _, parent_global, _, _)) = transform_query.get(parent.get()) {
+            let parent_global = *parent_global;
+            interpolate_recursive(
+                Some(&parent_global),
+                &mut transform_query,
+                &children_query,
+                &inherited_query,
+                entity,
+                Some(parent.get()),
+                inherited.unwrap_or(PropagateTransform2::ALL),
+                s,
+            );
+        }
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn interpolate_recursive(
+    parent: Option<&GlobalTransform2>,
+    transform_query: &mut Query<(
+        &Transform2,
+        Option<&PreviousTransform2>,
+        Option<&Pivot2>,
+        Option<&PropagationWeights2>,
+        &mut GlobalTransform2,
+        Option<&PropagateTransform2>,
+        Option<&Parent>,
+    )>,
+    children_query: &Query<&Children>,
+    inherited_query: &Query<&InheritedPropagation2>,
+    entity: Entity,
+    expected_parent: Option<Entity>,
+    inherited: PropagateTransform2,
+    s: Real,
+) {
+    let global_transform2 = {
+        let Ok((
+            transform2,
+            previous,
+            pivot,
+            weights,
+            mut global_transform2,
+            propagate,
+            child_parent,
+        )) = transform_query.get_mut(entity)
+        else {
+            return;
+        };
+        // malformed hierarchies are reported by the propagation system
+        if child_parent.map(Parent::get) != expected_parent {
+            return;
+        }
+        let transform2 = previous.map_or(*transform2, |previous| previous.0.lerp(*transform2, s));
+        let transform2 = local_transform(&transform2, pivot);
+        *global_transform2 = match parent {
+            Some(parent) => propagate_child(
+                parent,
+                transform2,
+                propagate.copied().unwrap_or(inherited),
+                weights,
+            ),
+            None => transform2.into(),
+        };
+        *global_transform2
+    };
+    let inherited = inherited_query
+        .get(entity)
+        .map_or(inherited, |inherited| inherited.0);
+    if let Ok(children) = children_query.get(entity) {
+        for child in children {
+            interpolate_recursive(
+                Some(&global_transform2),
+                transform_query,
+                children_query,
+                inherited_query,
+                *child,
+                Some(entity),
+                inherited,
+                s,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::assert_near;
+    use crate::test_util::translation;
+    use bevy::time::FixedTimestep;
+    use bevy::utils::Duration;
+    use bevy::utils::Instant;
+
+    #[derive(StageLabel)]
+    struct FixedUpdateStage;
+
+    const TIMESTEP_LABEL: &str = "fixed";
+
+    #[test]
+    fn interpolate() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<FixedTimesteps>()
+            .add_plugin(Transform2dPlugin::default())
+            .add_stage_after(
+                CoreStage::Update,
+                FixedUpdateStage,
+                SystemStage::parallel()
+                    .with_run_criteria(FixedTimestep::step(1.).with_label(TIMESTEP_LABEL))
+                    .with_system(
+                        |mut query: Query<&mut Transform2, With<PreviousTransform2>>| {
+                            for mut transform2 in query.iter_mut() {
+                                transform2.translation.x += 4.;
+                            }
+                        },
+                    ),
+            )
+            .add_plugin(Transform2InterpolationPlugin::new(
+                FixedUpdateStage,
+                TIMESTEP_LABEL,
+            ));
+        let entity = app
+            .world
+            .spawn((TransformBundle2::default(), PreviousTransform2::default()))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(0., 1.)))
+            .id();
+        app.world.entity_mut(entity).push_children(&[child]);
+
+        let start = Instant::now();
+        let update = |app: &mut App, elapsed: f64| {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs_f64(elapsed));
+            app.update();
+        };
+        update(&mut app, 0.);
+        assert_near(translation(&app, entity), RealVec2::ZERO);
+
+        // one step from 0 to 4, with a quarter of the next step accumulated
+        update(&mut app, 1.25);
+        assert_near(translation(&app, entity), RealVec2::new(1., 0.));
+        assert_near(translation(&app, child), RealVec2::new(1., 1.));
+        assert_eq!(
+            app.world.get::<Transform2>(entity).unwrap().translation,
+            RealVec2::new(4., 0.)
+        );
+
+        update(&mut app, 1.75);
+        assert_near(translation(&app, entity), RealVec2::new(3., 0.));
+        assert_near(translation(&app, child), RealVec2::new(3., 1.));
+        assert_eq!(
+            app.world.get::<Transform2>(entity).unwrap().translation,
+            RealVec2::new(4., 0.)
+        );
+        assert_eq!(
+            app.world.get::<Transform2>(child).unwrap().translation,
+            RealVec2::new(0., 1.)
+        );
+    }
+}
@@ -1,16 +1,48 @@
 pub mod bundles;
+pub mod commands;
+pub mod floating_origin;
+pub mod helper;
+pub mod hierarchy;
+pub mod interpolation;
+pub mod mixed;
+pub mod pivot;
+pub mod pixel_snap;
+pub mod precision;
+pub mod rotation2;
+pub mod sync;
 pub mod systems;
+#[cfg(test)]
+mod test_util;
 pub mod transform2;
 
+use bevy::ecs::schedule::StageLabelId;
 use bevy::prelude::*;
+use bevy::render::view::VisibilitySystems;
+use bevy::transform::TransformSystem;
+use rotation2::Rotation2;
 use transform2::GlobalTransform2;
 use transform2::PropagateTransform2;
 use transform2::Transform2;
 
 pub mod prelude {
     pub use crate::bundles::*;
+    pub use crate::commands::BuildChildrenInPlace2;
+    pub use crate::floating_origin::FloatingOriginPlugin;
+    pub use crate::floating_origin::OriginShifted2;
+    pub use crate::helper::ComputeGlobalTransform2Error;
+    pub use crate::helper::TransformHelper2;
+    pub use crate::hierarchy::HierarchyError2;
+    pub use crate::hierarchy::HierarchyErrorPolicy2;
+    pub use crate::interpolation::PreviousTransform2;
+    pub use crate::interpolation::Transform2InterpolationPlugin;
+    pub use crate::pivot::Pivot2;
+    pub use crate::pixel_snap::PixelSnap2;
+    pub use crate::rotation2::Rotation2;
+    pub use crate::sync::SyncTransform2;
     pub use crate::transform2::GlobalTransform2;
+    pub use crate::transform2::InheritedPropagation2;
     pub use crate::transform2::PropagateTransform2;
+    pub use crate::transform2::PropagationWeights2;
     pub use crate::transform2::Transform2;
     pub use crate::Transform2dPlugin;
 }
@@ -21,31 +53,193 @@ pub enum Transform2dSystem {
     /// Propagates changes in transform to children's [`GlobalTransform`](crate::components::GlobalTransform)
     PropagateTransform2System,
     DeriveGlobalTransformSystem,
+    /// Blends [`PreviousTransform2`](crate::interpolation::PreviousTransform2) into the
+    /// [`GlobalTransform2`] of entities updated on a fixed timestep
+    InterpolateTransform2System,
+    /// Moves the [`RenderOrigin2`](crate::systems::RenderOrigin2) to the active camera
+    UpdateRenderOriginSystem,
+    /// Shifts root entities to keep the active camera near the origin
+    FloatingOriginSystem,
+    /// Reports entities that have become their own ancestor
+    DetectHierarchyCyclesSystem,
+    /// Inserts the global transforms missing from entities with a [`Transform2`]
+    InsertMissingGlobalTransformsSystem,
+    /// Propagates transforms between [`Transform`] and [`Transform2`] entities in the same hierarchy
+    PropagateMixedHierarchiesSystem,
+    /// Synchronizes the [`Transform`] and [`Transform2`] of entities with a
+    /// [`SyncTransform2`](crate::sync::SyncTransform2)
+    SyncTransform2System,
 }
 
-/// The base plugin for handling [`Transform`] components
-#[derive(Default)]
-pub struct Transform2dPlugin;
+/// The stage [`Transform2dPlugin`] was built to run its systems in.
+///
+/// Inserted by [`Transform2dPlugin`], so plugins built after it add their systems to the same stage.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transform2dStage(pub StageLabelId);
+
+impl Transform2dStage {
+    /// The stage of the [`Transform2dPlugin`] already added to `app`, or
+    /// [`CoreStage::PostUpdate`] if there isn't one.
+    pub(crate) fn of(app: &App) -> StageLabelId {
+        app.world
+            .get_resource::<Self>()
+            .map_or(CoreStage::PostUpdate.as_label(), |stage| stage.0)
+    }
+}
+
+/// The base plugin for handling [`Transform2`] components.
+///
+/// By default the systems run in [`CoreStage::PostUpdate`], with an extra propagation pass in
+/// [`StartupStage::PostStartup`] so that entities spawned at startup have the right global
+/// transforms in the first frame. Use the builder methods to change this.
+pub struct Transform2dPlugin {
+    stage: StageLabelId,
+    startup_propagation: bool,
+    derive_global_transform: bool,
+    extra_propagation_stages: Vec<StageLabelId>,
+}
+
+impl Default for Transform2dPlugin {
+    fn default() -> Self {
+        Self {
+            stage: CoreStage::PostUpdate.as_label(),
+            startup_propagation: true,
+            derive_global_transform: true,
+            extra_propagation_stages: Vec::new(),
+        }
+    }
+}
+
+impl Transform2dPlugin {
+    /// Runs the systems in `stage` instead of [`CoreStage::PostUpdate`].
+    ///
+    /// They're only ordered against Bevy's [`TransformSystem`] and [`VisibilitySystems`] labels in
+    /// [`CoreStage::PostUpdate`], where those systems run. The stage is stored in the
+    /// [`Transform2dStage`] resource, and [`FloatingOriginPlugin`](crate::floating_origin::FloatingOriginPlugin)
+    /// and [`Transform2InterpolationPlugin`](crate::interpolation::Transform2InterpolationPlugin)
+    /// add their systems to it, so add them after this plugin.
+    #[must_use]
+    pub fn in_stage(mut self, stage: impl StageLabel) -> Self {
+        self.stage = stage.as_label();
+        self
+    }
+
+    /// Doesn't propagate transforms in [`StartupStage::PostStartup`].
+    #[must_use]
+    pub fn without_startup_propagation(mut self) -> Self {
+        self.startup_propagation = false;
+        self
+    }
+
+    /// Doesn't derive the [`GlobalTransform`] of 2D entities from their [`GlobalTransform2`],
+    /// for apps that render them some other way.
+    #[must_use]
+    pub fn without_global_transform_derivation(mut self) -> Self {
+        self.derive_global_transform = false;
+        self
+    }
+
+    /// Also propagates [`Transform2`] changes in `stage`, so systems later in that stage,
+    /// like gameplay systems in a fixed timestep, read an up to date [`GlobalTransform2`].
+    #[must_use]
+    pub fn with_propagation_in_stage(mut self, stage: impl StageLabel) -> Self {
+        self.extra_propagation_stages.push(stage.as_label());
+        self
+    }
+}
 
 impl Plugin for Transform2dPlugin {
     fn build(&self, app: &mut App) {
+        let stage = self.stage;
         app.register_type::<Transform2>()
+            .register_type::<Rotation2>()
             .register_type::<GlobalTransform2>()
             .register_type::<PropagateTransform2>()
+            .register_type::<transform2::InheritedPropagation2>()
+            .register_type::<transform2::PropagationWeights2>()
+            .register_type::<pivot::Pivot2>()
+            .register_type::<pixel_snap::PixelSnap2>()
+            .register_type::<sync::SyncTransform2>()
+            .insert_resource(Transform2dStage(stage))
+            .init_resource::<systems::RenderOrigin2>()
+            .init_resource::<hierarchy::HierarchyErrorPolicy2>()
+            .add_event::<hierarchy::HierarchyError2>()
+            .add_system_to_stage(
+                stage,
+                systems::insert_missing_global_transforms_system
+                    .at_start()
+                    .label(Transform2dSystem::InsertMissingGlobalTransformsSystem),
+            )
+            .add_system_to_stage(
+                stage,
+                sync::sync_transform2_system
+                    .at_start()
+                    .label(Transform2dSystem::SyncTransform2System)
+                    .after(Transform2dSystem::InsertMissingGlobalTransformsSystem),
+            )
+            .add_system_to_stage(stage, systems::warn_transform_conflicts_system)
+            .add_system_to_stage(
+                stage,
+                hierarchy::detect_hierarchy_cycles_system
+                    .label(Transform2dSystem::DetectHierarchyCyclesSystem)
+                    .before(Transform2dSystem::PropagateTransform2System),
+            )
+            .add_system_to_stage(
+                stage,
+                systems::transform_2d_propagate_system
+                    .label(Transform2dSystem::PropagateTransform2System),
+            );
+
+        let mut propagate_mixed = mixed::propagate_mixed_hierarchies_system
+            .label(Transform2dSystem::PropagateMixedHierarchiesSystem)
+            .after(Transform2dSystem::PropagateTransform2System);
+        let mut derive = systems::derive_global_transform
+            .label(Transform2dSystem::DeriveGlobalTransformSystem)
+            .after(Transform2dSystem::PropagateTransform2System)
+            .after(Transform2dSystem::PropagateMixedHierarchiesSystem);
+        if stage == CoreStage::PostUpdate.as_label() {
+            propagate_mixed = propagate_mixed.after(TransformSystem::TransformPropagate);
+            derive = derive
+                .after(TransformSystem::TransformPropagate)
+                .before(VisibilitySystems::UpdateOrthographicFrusta)
+                .before(VisibilitySystems::UpdatePerspectiveFrusta)
+                .before(VisibilitySystems::UpdateProjectionFrusta)
+                .before(VisibilitySystems::CheckVisibility);
+        }
+        app.add_system_to_stage(stage, propagate_mixed);
+        if self.derive_global_transform {
+            app.add_system_to_stage(stage, derive);
+        }
+
+        #[cfg(feature = "f64")]
+        app.add_system_to_stage(
+            stage,
+            systems::update_render_origin_system
+                .label(Transform2dSystem::UpdateRenderOriginSystem)
+                .after(Transform2dSystem::PropagateTransform2System)
+                .before(Transform2dSystem::PropagateMixedHierarchiesSystem),
+        );
+
+        if self.startup_propagation {
+            app.add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                systems::insert_missing_global_transforms_system
+                    .at_start()
+                    .label(Transform2dSystem::InsertMissingGlobalTransformsSystem),
+            )
             .add_startup_system_to_stage(
                 StartupStage::PostStartup,
                 systems::transform_2d_propagate_system
                     .label(Transform2dSystem::PropagateTransform2System),
-            )
-            .add_system_to_stage(
-                CoreStage::PostUpdate,
+            );
+        }
+
+        for &extra_stage in &self.extra_propagation_stages {
+            app.add_system_to_stage(
+                extra_stage,
                 systems::transform_2d_propagate_system
                     .label(Transform2dSystem::PropagateTransform2System),
-            )
-            .add_system_to_stage(
-                CoreStage::PostUpdate,
-                systems::derive_global_transform
-                    .label(Transform2dSystem::DeriveGlobalTransformSystem),
             );
+        }
     }
 }
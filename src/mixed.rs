@@ -0,0 +1,405 @@
+use crate::pivot::Pivot2;
+use crate::precision::*;
+use crate::prelude::*;
+use crate::sync::SyncTransform2;
+use crate::systems::local_transform;
+use crate::systems::propagate_child;
+use crate::systems::RemovedInputs;
+use crate::systems::RenderOrigin2;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// The global transform of the parent of an entity in a mixed hierarchy.
+#[derive(Clone, Copy)]
+pub(crate) enum MixedParent {
+    Global2(GlobalTransform2),
+    Global(GlobalTransform),
+}
+
+/// The [`Transform2`] query of [`propagate_mixed_hierarchies_system`].
+type Transform2Query<'w, 's> = Query<
+    'w,
+    's,
+    (
+        (&'static Transform2, Changed<Transform2>),
+        Option<(&'static Pivot2, Changed<Pivot2>)>,
+        Option<(&'static PropagationWeights2, Changed<PropagationWeights2>)>,
+        Option<(&'static PropagateTransform2, Changed<PropagateTransform2>)>,
+        Option<(
+            &'static InheritedPropagation2,
+            Changed<InheritedPropagation2>,
+        )>,
+        &'static mut GlobalTransform2,
+        Option<&'static Parent>,
+    ),
+    Or<(Without<Transform>, With<SyncTransform2>)>,
+>;
+
+/// The [`Transform`] query of [`propagate_mixed_hierarchies_system`].
+type TransformQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        (&'static Transform, Changed<Transform>),
+        &'static mut GlobalTransform,
+        Option<&'static Parent>,
+    ),
+    Without<Transform2>,
+>;
+
+/// Propagates transforms across the boundaries between [`Transform`] and [`Transform2`] entities.
+///
+/// A [`Transform`] entity with a [`GlobalTransform2`] parent gets its [`GlobalTransform`] from the
+/// parent's render transform, so with the `f64` feature it's relative to the [`RenderOrigin2`].
+/// A [`Transform2`] entity with a [`GlobalTransform`] parent inherits the parent's transform
+/// projected onto the XY plane by [`GlobalTransform2::from_render_transform`].
+///
+/// Bevy's `transform_propagate_system` and [`transform_2d_propagate_system`](crate::systems::transform_2d_propagate_system)
+/// each stop at the first entity of the other kind, this system continues from there through the rest
+/// of the subtree. The boundaries are tracked from changes to [`Parent`] and to the transform components,
+/// and only the entities whose inputs changed since the last run are recomputed.
+///
+/// Bevy's system also propagates the [`Transform`] children of [`SyncTransform2`] entities, from the
+/// synced [`Transform`] rather than the [`GlobalTransform2`]. Those [`GlobalTransform`]s are
+/// recomputed here whenever Bevy writes them, so the 2D hierarchy always has the last word.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn propagate_mixed_hierarchies_system(
+    render_origin: Res<RenderOrigin2>,
+    mut boundaries: Local<HashSet<Entity>>,
+    moved_query: Query<
+        Entity,
+        Or<(
+            Changed<Parent>,
+            Added<Transform>,
+            Added<Transform2>,
+            Added<SyncTransform2>,
+        )>,
+    >,
+    removed_transforms: RemovedComponents<Transform>,
+    removed_transforms2: RemovedComponents<Transform2>,
+    removed_syncs: RemovedComponents<SyncTransform2>,
+    removed_pivots: RemovedComponents<Pivot2>,
+    removed_weights: RemovedComponents<PropagationWeights2>,
+    removed_propagations: RemovedComponents<PropagateTransform2>,
+    removed_inherited: RemovedComponents<InheritedPropagation2>,
+    ancestor_query: Query<(
+        Option<&Parent>,
+        Option<&Transform2>,
+        Option<&Transform>,
+        Option<&SyncTransform2>,
+        Option<&InheritedPropagation2>,
+    )>,
+    mut transform2_query: Transform2Query,
+    mut transform_query: TransformQuery,
+    children_query: Query<&Children>,
+) {
+    let is_2d = |entity| {
+        ancestor_query
+            .get(entity)
+            .is_ok_and(|(_, transform2, transform, sync, _)| {
+                transform2.is_some() && (transform.is_none() || sync.is_some())
+            })
+    };
+    let is_boundary = |entity| {
+        ancestor_query
+            .get(entity)
+            .is_ok_and(|(parent, transform2, transform, ..)| {
+                (transform2.is_some() || transform.is_some())
+                    && parent.is_some_and(|parent| is_2d(entity) != is_2d(parent.get()))
+            })
+    };
+
+    // entities that may have become boundaries, with the children of those whose kind changed
+    let mut moved = HashSet::new();
+    for entity in removed_transforms
+        .iter()
+        .chain(removed_transforms2.iter())
+        .chain(removed_syncs.iter())
+        .chain(moved_query.iter())
+    {
+        moved.insert(entity);
+        if let Ok(children) = children_query.get(entity) {
+            moved.extend(children.iter().copied());
+        }
+    }
+    boundaries.extend(moved.iter().copied().filter(|&entity| is_boundary(entity)));
+    boundaries.retain(|&entity| is_boundary(entity));
+
+    let removed = RemovedInputs {
+        transform: removed_pivots
+            .iter()
+            .chain(removed_weights.iter())
+            .chain(removed_propagations.iter())
+            .collect(),
+        inherited: removed_inherited.iter().collect(),
+    };
+
+    let mut visited = HashSet::new();
+    for &entity in boundaries.iter() {
+        let Ok((Some(parent), ..)) = ancestor_query.get(entity) else {
+            continue;
+        };
+        let parent_is_2d = is_2d(parent.get());
+
+        // only start from boundaries whose parent was propagated by one of the regular systems,
+        // boundaries further down are reached by the recursion
+        let mut ancestor = parent.get();
+        let mut inherited = None;
+        let mut is_top_boundary = true;
+        visited.clear();
+        while let Ok((next, _, _, _, ancestor_inherited)) = ancestor_query.get(ancestor) {
+            if is_2d(ancestor) != parent_is_2d || !visited.insert(ancestor) {
+                is_top_boundary = false;
+                break;
+            }
+            if inherited.is_none() {
+                inherited = ancestor_inherited.map(|inherited| inherited.0);
+            }
+            match next {
+                Some(next) => ancestor = next.get(),
+                None => break,
+            }
+        }
+        if !is_top_boundary {
+            continue;
+        }
+
+        let parent_global = if parent_is_2d {
+            transform2_query
+                .get_mut(parent.get())
+                .map(|(.., global_transform2, _)| {
+                    (
+                        MixedParent::Global2(*global_transform2),
+                        global_transform2.is_changed(),
+                    )
+                })
+        } else {
+            transform_query
+                .get_mut(parent.get())
+                .map(|(_, global_transform, _)| {
+                    (
+                        MixedParent::Global(*global_transform),
+                        global_transform.is_changed(),
+                    )
+                })
+        };
+        if let Ok((parent_global, parent_changed)) = parent_global {
+            propagate_mixed_recursive(
+                render_origin.0,
+                &mut transform2_query,
+                &mut transform_query,
+                &children_query,
+                &removed,
+                &moved,
+                parent_global,
+                entity,
+                parent.get(),
+                inherited.unwrap_or(PropagateTransform2::ALL),
+                parent_changed || render_origin.is_changed(),
+            );
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn propagate_mixed_recursive(
+    origin: RealVec2,
+    transform2_query: &mut Transform2Query,
+    transform_query: &mut TransformQuery,
+    children_query: &Query<&Children>,
+    removed: &RemovedInputs,
+    moved: &HashSet<Entity>,
+    parent: MixedParent,
+    entity: Entity,
+    expected_parent: Entity,
+    mut inherited: PropagateTransform2,
+    mut changed: bool,
+) {
+    changed |= moved.contains(&entity);
+    let global = if let Ok((
+        (transform2, transform2_changed),
+        pivot,
+        weights,
+        propagate,
+        entity_inherited,
+        mut global_transform2,
+        child_parent,
+    )) = transform2_query.get_mut(entity)
+    {
+        if child_parent.map(Parent::get) != Some(expected_parent) {
+            return;
+        }
+        changed |= transform2_changed
+            || pivot.is_some_and(|(_, changed)| changed)
+            || weights.is_some_and(|(_, changed)| changed)
+            || propagate.is_some_and(|(_, changed)| changed)
+            || global_transform2.is_added()
+            || removed.transform.contains(&entity);
+        if changed {
+            let parent = match parent {
+                MixedParent::Global2(global_transform2) => global_transform2,
+                MixedParent::Global(global_transform) => {
+                    GlobalTransform2::from_render_transform(&global_transform, origin)
+                }
+            };
+            let propagated = propagate_child(
+                &parent,
+                local_transform(transform2, pivot.map(|(pivot, _)| pivot)),
+                propagate.map_or(inherited, |(propagate, _)| *propagate),
+                weights.map(|(weights, _)| weights),
+            );
+            if *global_transform2 != propagated {
+                *global_transform2 = propagated;
+            }
+        }
+        changed |= entity_inherited.is_some_and(|(_, changed)| changed)
+            || removed.inherited.contains(&entity);
+        inherited = entity_inherited.map_or(inherited, |(entity_inherited, _)| entity_inherited.0);
+        MixedParent::Global2(*global_transform2)
+    } else if let Ok(((transform, transform_changed), mut global_transform, child_parent)) =
+        transform_query.get_mut(entity)
+    {
+        if child_parent.map(Parent::get) != Some(expected_parent) {
+            return;
+        }
+        // also overwrites the values Bevy's propagation wrote under synced entities
+        changed |= transform_changed || global_transform.is_changed();
+        if changed {
+            let parent = match parent {
+                MixedParent::Global2(global_transform2) => {
+                    global_transform2.to_render_transform(origin)
+                }
+                MixedParent::Global(global_transform) => global_transform,
+            };
+            let propagated = parent.mul_transform(*transform);
+            if *global_transform != propagated {
+                *global_transform = propagated;
+            }
+        }
+        MixedParent::Global(*global_transform)
+    } else {
+        return;
+    };
+
+    if let Ok(children) = children_query.get(entity) {
+        for child in children {
+            propagate_mixed_recursive(
+                origin,
+                transform2_query,
+                transform_query,
+                children_query,
+                removed,
+                moved,
+                global,
+                *child,
+                entity,
+                inherited,
+                changed,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::assert_near;
+    use crate::test_util::translation;
+    use bevy::transform::TransformPlugin;
+
+    #[test]
+    fn mixed_hierarchy() {
+        let mut app = App::new();
+        app.add_plugin(TransformPlugin)
+            .add_plugin(Transform2dPlugin::default());
+        let root2 = app
+            .world
+            .spawn(TransformBundle2::from(
+                Transform2::from_xy(10., 0.).with_rotation(Rotation2::degrees(90.)),
+            ))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle::from(Transform::from_xyz(1., 0., 2.)))
+            .id();
+        let grandchild2 = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(1., 0.)))
+            .id();
+        app.world.entity_mut(root2).push_children(&[child]);
+        app.world.entity_mut(child).push_children(&[grandchild2]);
+        app.update();
+
+        let child_global = app.world.get::<GlobalTransform>(child).unwrap();
+        assert!(child_global
+            .translation()
+            .abs_diff_eq(Vec3::new(10., 1., 2.), 0.001));
+        let grandchild_global2 = app.world.get::<GlobalTransform2>(grandchild2).unwrap();
+        assert_near(grandchild_global2.translation(), RealVec2::new(10., 2.));
+        assert_eq!(grandchild_global2.depth(), 2.);
+
+        app.world.get_mut::<Transform>(child).unwrap().translation.x = 2.;
+        app.update();
+        assert_near(translation(&app, grandchild2), RealVec2::new(10., 3.));
+        assert!(app
+            .world
+            .get::<GlobalTransform>(grandchild2)
+            .unwrap()
+            .translation()
+            .abs_diff_eq(Vec3::new(10., 3., 2.), 0.001));
+    }
+
+    #[test]
+    fn mixed_change_detection() {
+        let mut app = App::new();
+        app.add_plugin(TransformPlugin)
+            .add_plugin(Transform2dPlugin::default());
+        let root2 = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(10., 0.)))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle::from(Transform::from_xyz(1., 0., 0.)))
+            .id();
+        let grandchild2 = app
+            .world
+            .spawn(TransformBundle2::from(Transform2::from_xy(1., 0.)))
+            .id();
+        app.world.entity_mut(root2).push_children(&[child]);
+        app.world.entity_mut(child).push_children(&[grandchild2]);
+        app.update();
+
+        // nothing changed, so nothing is recomputed
+        let stale = GlobalTransform2::from(Transform2::from_xy(-5., -5.));
+        *app.world.get_mut::<GlobalTransform2>(grandchild2).unwrap() = stale;
+        app.update();
+        assert_eq!(
+            *app.world.get::<GlobalTransform2>(grandchild2).unwrap(),
+            stale
+        );
+
+        app.world
+            .get_mut::<Transform2>(root2)
+            .unwrap()
+            .translation
+            .y = 3.;
+        app.update();
+        assert_near(translation(&app, grandchild2), RealVec2::new(12., 3.));
+
+        // a boundary added after the first frame
+        let late = app
+            .world
+            .spawn(TransformBundle::from(Transform::from_xyz(0., 1., 0.)))
+            .id();
+        app.update();
+        app.world.entity_mut(root2).push_children(&[late]);
+        app.update();
+        assert!(app
+            .world
+            .get::<GlobalTransform>(late)
+            .unwrap()
+            .translation()
+            .abs_diff_eq(Vec3::new(10., 4., 0.), 0.001));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::precision::*;
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A point in the local space of an entity that its rotation, scale and flips are applied about.
+///
+/// The propagation systems move the entity so that the pivot stays where it would be
+/// without them, like a door turning on its hinge. The resulting [`GlobalTransform2`] is
+/// the frame its children are propagated from, so they turn and scale with it.
+/// [`Transform2`] itself is left untouched.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Pivot2(pub RealVec2);
+
+impl Pivot2 {
+    #[inline]
+    #[must_use]
+    pub fn new(x: Real, y: Real) -> Self {
+        Self(RealVec2::new(x, y))
+    }
+
+    /// Offsets the translation of `transform2` so that its rotation, scale and flips happen about the pivot.
+    #[inline]
+    #[must_use]
+    pub fn apply(&self, mut transform2: Transform2) -> Transform2 {
+        transform2.translation += self.0 - transform2.rotation_scale_matrix() * self.0;
+        transform2
+    }
+
+    /// The inverse of [`Pivot2::apply`], recovers the [`Transform2`] that `apply` turned into `pivoted`.
+    #[inline]
+    #[must_use]
+    pub fn unapply(&self, mut pivoted: Transform2) -> Transform2 {
+        pivoted.translation -= self.0 - pivoted.rotation_scale_matrix() * self.0;
+        pivoted
+    }
+}
+
+impl From<RealVec2> for Pivot2 {
+    #[inline]
+    fn from(pivot: RealVec2) -> Self {
+        Self(pivot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let pivot = Pivot2::new(1., 2.);
+        let transform2 = Transform2::from_xy(5., -3.)
+            .with_rotation(Rotation2::degrees(90.))
+            .with_scale(Transform2::IDENTITY.scale * 3.);
+        let pivoted = pivot.apply(transform2);
+        let fixed = Transform2::from_translation(transform2.translation).transform_point(pivot.0);
+        assert!((pivoted.transform_point(pivot.0) - fixed).length() < 0.001);
+        assert_eq!(pivoted.rotation, transform2.rotation);
+        assert_eq!(pivoted.scale, transform2.scale);
+        assert!((pivot.unapply(pivoted).translation - transform2.translation).length() < 0.001);
+    }
+}
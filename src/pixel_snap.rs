@@ -0,0 +1,130 @@
+use crate::precision::*;
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Snaps the render [`GlobalTransform`] of an entity to a pixel grid.
+///
+/// Only the transform derived for rendering is snapped, [`GlobalTransform2`] keeps the
+/// precise values for gameplay. The grid is aligned with the
+/// [`RenderOrigin2`](crate::systems::RenderOrigin2), so with the `f64` feature it moves with the camera.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct PixelSnap2 {
+    /// The size of a grid cell, translations are rounded to the nearest multiple on each axis.
+    /// An axis with a size of zero isn't snapped.
+    pub grid: Vec2,
+    /// If set, the rotation is rounded to the nearest multiple of this angle in radians.
+    pub rotation_step: Option<Real>,
+}
+
+impl PixelSnap2 {
+    /// Snaps translations to a square grid of cells `size` pixels wide.
+    #[inline]
+    #[must_use]
+    pub fn new(size: f32) -> Self {
+        Self {
+            grid: Vec2::splat(size),
+            rotation_step: None,
+        }
+    }
+
+    /// Also snaps the rotation to multiples of `step` radians.
+    #[inline]
+    #[must_use]
+    pub fn with_rotation_step(mut self, step: Real) -> Self {
+        self.rotation_step = Some(step);
+        self
+    }
+
+    /// Converts `global_transform2` to a render [`GlobalTransform`] relative to `origin`,
+    /// like [`GlobalTransform2::to_render_transform`], with the snapping applied.
+    #[must_use]
+    pub fn snap(&self, global_transform2: &GlobalTransform2, origin: RealVec2) -> GlobalTransform {
+        let mut affine = global_transform2.affine();
+        if let Some(step) = self.rotation_step.filter(|step| 0. < *step) {
+            let rotation = global_transform2.rotation();
+            let snapped = Rotation2::radians((rotation.as_radians() / step).round() * step);
+            affine.matrix2 = (snapped * rotation.inverse()).to_mat2() * affine.matrix2;
+        }
+        let mut render_affine = GlobalTransform2::from_affine(affine, global_transform2.depth())
+            .to_render_transform(origin)
+            .affine();
+        let translation = render_affine.translation.truncate();
+        render_affine.translation.x = snap_axis(translation.x, self.grid.x);
+        render_affine.translation.y = snap_axis(translation.y, self.grid.y);
+        render_affine.into()
+    }
+}
+
+impl Default for PixelSnap2 {
+    fn default() -> Self {
+        Self::new(1.)
+    }
+}
+
+#[inline]
+fn snap_axis(value: f32, size: f32) -> f32 {
+    if 0. < size {
+        (value / size).round() * size
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snap() {
+        let global_transform2 = GlobalTransform2::from(
+            Transform2::from_xyz(10.4, -3.6, 2.).with_rotation(Rotation2::degrees(40.)),
+        );
+        let snapped = PixelSnap2::new(2.)
+            .with_rotation_step(Rotation2::degrees(45.).as_radians())
+            .snap(&global_transform2, RealVec2::ZERO);
+        assert_eq!(snapped.translation(), Vec3::new(10., -4., 2.));
+        assert!(snapped
+            .compute_transform()
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(45f32.to_radians()), 0.001));
+
+        let unsnapped = PixelSnap2 {
+            grid: Vec2::ZERO,
+            rotation_step: None,
+        }
+        .snap(&global_transform2, RealVec2::ZERO);
+        assert!(unsnapped.affine().abs_diff_eq(
+            global_transform2
+                .to_render_transform(RealVec2::ZERO)
+                .affine(),
+            1e-5
+        ));
+    }
+
+    #[test]
+    fn remove_pixel_snap() {
+        let mut app = App::new();
+        app.add_plugin(Transform2dPlugin::default());
+        let entity = app
+            .world
+            .spawn((
+                TransformBundle2::from(Transform2::from_xy(10.4, 0.)),
+                PixelSnap2::default(),
+            ))
+            .id();
+        let render_x = |app: &App| {
+            app.world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation()
+                .x
+        };
+        app.update();
+        assert_eq!(render_x(&app), 10.);
+
+        app.world.entity_mut(entity).remove::<PixelSnap2>();
+        app.update();
+        assert!((render_x(&app) - 10.4).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,68 @@
+//! The floating point types used by [`Transform2`](crate::transform2::Transform2) and
+//! [`GlobalTransform2`](crate::transform2::GlobalTransform2).
+//!
+//! These are the `f32` glam types by default. The `f64` feature switches them to their
+//! double precision counterparts, for worlds large enough that `f32` translations jitter.
+//! Rendering still uses `f32`, see [`RenderOrigin2`](crate::systems::RenderOrigin2).
+
+use bevy::prelude::*;
+
+#[cfg(not(feature = "f64"))]
+mod types {
+    pub type Real = f32;
+    pub type RealVec2 = bevy::math::Vec2;
+    pub type RealVec3 = bevy::math::Vec3;
+    pub type RealMat2 = bevy::math::Mat2;
+    pub type RealAffine2 = bevy::math::Affine2;
+}
+
+#[cfg(feature = "f64")]
+mod types {
+    pub type Real = f64;
+    pub type RealVec2 = bevy::math::DVec2;
+    pub type RealVec3 = bevy::math::DVec3;
+    pub type RealMat2 = bevy::math::DMat2;
+    pub type RealAffine2 = bevy::math::DAffine2;
+}
+
+pub use types::*;
+
+#[inline]
+pub(crate) fn real_to_f32(value: Real) -> f32 {
+    #[cfg(not(feature = "f64"))]
+    return value;
+    #[cfg(feature = "f64")]
+    return value as f32;
+}
+
+#[inline]
+pub(crate) fn real_from_f32(value: f32) -> Real {
+    #[cfg(not(feature = "f64"))]
+    return value;
+    #[cfg(feature = "f64")]
+    return value as f64;
+}
+
+#[inline]
+pub(crate) fn real_from_f64(value: f64) -> Real {
+    #[cfg(not(feature = "f64"))]
+    return value as f32;
+    #[cfg(feature = "f64")]
+    return value;
+}
+
+#[inline]
+pub(crate) fn vec2_to_f32(value: RealVec2) -> Vec2 {
+    #[cfg(not(feature = "f64"))]
+    return value;
+    #[cfg(feature = "f64")]
+    return value.as_vec2();
+}
+
+#[inline]
+pub(crate) fn vec2_from_f32(value: Vec2) -> RealVec2 {
+    #[cfg(not(feature = "f64"))]
+    return value;
+    #[cfg(feature = "f64")]
+    return value.as_dvec2();
+}
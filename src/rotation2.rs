@@ -0,0 +1,258 @@
+use crate::precision::*;
+use bevy::prelude::*;
+
+/// A 2D rotation.
+///
+/// Stored as the cosine and sine of the angle, so composing rotations and building
+/// matrices don't need any trigonometry and the angle can't grow without bound.
+/// [`Rotation2::as_radians`] always returns an angle in the range `(-PI, PI]`.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub struct Rotation2 {
+    cos: Real,
+    sin: Real,
+}
+
+impl Rotation2 {
+    pub const IDENTITY: Self = Self { cos: 1., sin: 0. };
+
+    /// Creates a rotation from an angle in radians.
+    #[inline]
+    #[must_use]
+    pub fn radians(radians: Real) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self { cos, sin }
+    }
+
+    /// Creates a rotation from an angle in degrees.
+    #[inline]
+    #[must_use]
+    pub fn degrees(degrees: Real) -> Self {
+        Self::radians(degrees.to_radians())
+    }
+
+    /// Creates a rotation from the sine and cosine of an angle.
+    ///
+    /// The values are normalized, so they don't need to be exact.
+    #[inline]
+    #[must_use]
+    pub fn from_sin_cos(sin: Real, cos: Real) -> Self {
+        Self::from_direction(RealVec2::new(cos, sin))
+    }
+
+    /// Creates the rotation that turns the x axis to point along `direction`.
+    ///
+    /// Returns [`Rotation2::IDENTITY`] if `direction` is zero.
+    #[inline]
+    #[must_use]
+    pub fn from_direction(direction: RealVec2) -> Self {
+        let direction = direction.normalize_or_zero();
+        if direction == RealVec2::ZERO {
+            return Self::IDENTITY;
+        }
+        Self {
+            cos: direction.x,
+            sin: direction.y,
+        }
+    }
+
+    /// The angle of the rotation in radians, in the range `(-PI, PI]`.
+    #[inline]
+    #[must_use]
+    pub fn as_radians(self) -> Real {
+        // `atan2` returns -PI for a sine of -0.0, which `inverse` and `mul` can produce
+        let sin = if self.sin == 0. { 0. } else { self.sin };
+        sin.atan2(self.cos)
+    }
+
+    /// The angle of the rotation in degrees, in the range `(-180, 180]`.
+    #[inline]
+    #[must_use]
+    pub fn as_degrees(self) -> Real {
+        self.as_radians().to_degrees()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn sin(self) -> Real {
+        self.sin
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn cos(self) -> Real {
+        self.cos
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn sin_cos(self) -> (Real, Real) {
+        (self.sin, self.cos)
+    }
+
+    /// The direction the x axis points in after the rotation.
+    #[inline]
+    #[must_use]
+    pub fn direction(self) -> RealVec2 {
+        RealVec2::new(self.cos, self.sin)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self {
+            cos: self.cos,
+            sin: -self.sin,
+        }
+    }
+
+    /// The signed angle of the shortest rotation from `self` to `rhs`, in the range `(-PI, PI]`.
+    #[inline]
+    #[must_use]
+    pub fn angle_to(self, rhs: Self) -> Real {
+        (rhs * self.inverse()).as_radians()
+    }
+
+    /// Interpolates between `self` and `rhs` by `s` along the shortest arc.
+    #[inline]
+    #[must_use]
+    pub fn slerp(self, rhs: Self, s: Real) -> Self {
+        Self::radians(self.angle_to(rhs) * s) * self
+    }
+
+    /// Turns `self` towards `target` along the shortest arc by at most `max_radians`.
+    #[inline]
+    #[must_use]
+    pub fn rotate_towards(self, target: Self, max_radians: Real) -> Self {
+        let angle = self.angle_to(target);
+        if angle.abs() <= max_radians {
+            target
+        } else {
+            Self::radians(max_radians.copysign(angle)) * self
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn to_mat2(self) -> RealMat2 {
+        RealMat2::from_cols(
+            RealVec2::new(self.cos, self.sin),
+            RealVec2::new(-self.sin, self.cos),
+        )
+    }
+}
+
+impl Default for Rotation2 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Real> for Rotation2 {
+    /// Creates a rotation from an angle in radians.
+    #[inline]
+    fn from(radians: Real) -> Self {
+        Self::radians(radians)
+    }
+}
+
+impl std::ops::Mul for Rotation2 {
+    type Output = Self;
+
+    /// Composes two rotations, renormalizing the result so errors don't accumulate.
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let cos = self.cos * rhs.cos - self.sin * rhs.sin;
+        let sin = self.sin * rhs.cos + self.cos * rhs.sin;
+        let length_recip = (cos * cos + sin * sin).sqrt().recip();
+        Self {
+            cos: cos * length_recip,
+            sin: sin * length_recip,
+        }
+    }
+}
+
+impl std::ops::MulAssign for Rotation2 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::Mul<RealVec2> for Rotation2 {
+    type Output = RealVec2;
+
+    #[inline]
+    fn mul(self, rhs: RealVec2) -> Self::Output {
+        RealVec2::new(
+            self.cos * rhs.x - self.sin * rhs.y,
+            self.sin * rhs.x + self.cos * rhs.y,
+        )
+    }
+}
+
+impl std::fmt::Display for Rotation2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_radians().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_near(a: Real, b: Real) {
+        assert!((a - b).abs() < 0.001, "{a} != {b}");
+    }
+
+    #[test]
+    fn constructors() {
+        assert_near(
+            Rotation2::degrees(90.).as_radians(),
+            std::f64::consts::FRAC_PI_2 as Real,
+        );
+        assert_near(
+            Rotation2::radians(-1.).as_degrees(),
+            (-1. as Real).to_degrees(),
+        );
+        assert_eq!(Rotation2::from(0.5), Rotation2::radians(0.5));
+
+        // normalized
+        let rotation = Rotation2::from_sin_cos(2., 2.);
+        assert_near(rotation.as_degrees(), 45.);
+        assert_near(rotation.direction().length(), 1.);
+        assert_eq!(
+            Rotation2::from_direction(RealVec2::new(0., 3.)).direction(),
+            RealVec2::Y
+        );
+        assert_eq!(
+            Rotation2::from_direction(RealVec2::ZERO),
+            Rotation2::IDENTITY
+        );
+    }
+
+    #[test]
+    fn half_turn() {
+        let pi = std::f64::consts::PI as Real;
+        let half_turn = Rotation2::from_sin_cos(0., -1.);
+        assert_eq!(half_turn.as_radians(), pi);
+        // the inverse has a sine of -0.0
+        assert_eq!(half_turn.inverse().as_radians(), pi);
+        assert_eq!((half_turn * Rotation2::IDENTITY.inverse()).as_radians(), pi);
+        assert_eq!(Rotation2::from_sin_cos(-0., 1.).as_radians(), 0.);
+        assert!(Rotation2::from_sin_cos(-0.001, -1.).as_radians() < 0.);
+    }
+
+    #[test]
+    fn slerp() {
+        let a = Rotation2::degrees(170.);
+        let b = Rotation2::degrees(-170.);
+        // across PI rather than back through 0
+        assert_near(a.slerp(b, 0.5).as_degrees().abs(), 180.);
+        assert_near(a.slerp(b, 0.25).as_degrees(), 175.);
+        assert_near(b.slerp(a, 0.25).as_degrees(), -175.);
+        assert_near(a.angle_to(b), (20. as Real).to_radians());
+        assert_near(a.slerp(b, 0.).as_degrees(), 170.);
+        assert_near(a.slerp(b, 1.).as_degrees(), -170.);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::prelude::*;
+use bevy::ecs::component::ComponentTicks;
+use bevy::prelude::*;
+
+/// Keeps a [`Transform`] in sync with the [`Transform2`] of an entity, for plugins that
+/// only know about [`Transform`], like physics engines.
+///
+/// Each frame, before propagation, the [`Transform2`] is copied into the [`Transform`], or
+/// the other way around if the [`Transform`] was the one written since the last sync. If both
+/// were written, the most recent change wins. The [`Transform`] is inserted if it's missing.
+///
+/// The entity is otherwise a 2D entity: its [`GlobalTransform`] and those of its [`Transform`]
+/// children are still derived from its [`GlobalTransform2`], replacing the values Bevy's
+/// transform propagation computes from the synced [`Transform`]. With the `f64` feature,
+/// values written to the [`Transform`] only have `f32` precision.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct SyncTransform2;
+
+/// Copies the [`Transform2`] of [`SyncTransform2`] entities into their [`Transform`], or the
+/// [`Transform`] back into the [`Transform2`] when it was changed more recently.
+///
+/// Runs as an exclusive system to read the change ticks of both components.
+#[allow(clippy::type_complexity)]
+pub fn sync_transform2_system(
+    world: &mut World,
+    query: &mut QueryState<Entity, (With<SyncTransform2>, With<Transform2>)>,
+) {
+    let last_change_tick = world.last_change_tick();
+    let change_tick = world.change_tick();
+    let entities: Vec<Entity> = query.iter(world).collect();
+    for entity in entities {
+        let mut entity = world.entity_mut(entity);
+        let Some(ticks) = entity.get_change_ticks::<Transform>().copied() else {
+            let transform2 = *entity.get::<Transform2>().unwrap();
+            entity.insert(Transform::from(transform2));
+            continue;
+        };
+        let ticks2 = *entity.get_change_ticks::<Transform2>().unwrap();
+        let since_change = ticks_since_change(&ticks, last_change_tick, change_tick);
+        let since_change2 = ticks_since_change(&ticks2, last_change_tick, change_tick);
+        let transform_is_newer = match (since_change, since_change2) {
+            (Some(_), None) => true,
+            (Some(since_change), Some(since_change2)) => since_change < since_change2,
+            (None, _) => false,
+        };
+        if transform_is_newer {
+            let transform = *entity.get::<Transform>().unwrap();
+            let current = *entity.get::<Transform2>().unwrap();
+            // rewritten with the value last synced, converting back would only add rounding
+            if Transform::from(current) == transform {
+                continue;
+            }
+            let transform2 = current.projected_with_flip_axis_of(transform);
+            let mut current = entity.get_mut::<Transform2>().unwrap();
+            if *current != transform2 {
+                *current = transform2;
+            }
+        } else if since_change2.is_some() {
+            let transform = Transform::from(*entity.get::<Transform2>().unwrap());
+            let mut current = entity.get_mut::<Transform>().unwrap();
+            if *current != transform {
+                *current = transform;
+            }
+        }
+    }
+}
+
+/// How many ticks before `change_tick` the component was last changed, or `None` if it
+/// hasn't changed since `last_change_tick`.
+///
+/// [`ComponentTicks`] doesn't expose its ticks, so the age is found by bisecting the ticks
+/// since `last_change_tick` with [`ComponentTicks::is_changed`].
+fn ticks_since_change(
+    ticks: &ComponentTicks,
+    last_change_tick: u32,
+    change_tick: u32,
+) -> Option<u32> {
+    if !ticks.is_changed(last_change_tick, change_tick) {
+        return None;
+    }
+    // the component counts as changed since every tick older than its change
+    let mut low = 0;
+    let mut high = change_tick.wrapping_sub(last_change_tick);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if ticks.is_changed(change_tick.wrapping_sub(mid), change_tick) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Some(low)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::precision::*;
+    use crate::systems::RenderOrigin2;
+    use bevy::transform::TransformPlugin;
+
+    fn transform2(app: &App, entity: Entity) -> Transform2 {
+        *app.world.get::<Transform2>(entity).unwrap()
+    }
+
+    fn transform(app: &App, entity: Entity) -> Transform {
+        *app.world.get::<Transform>(entity).unwrap()
+    }
+
+    fn global_translation(app: &App, entity: Entity) -> Vec3 {
+        app.world
+            .get::<GlobalTransform>(entity)
+            .unwrap()
+            .translation()
+    }
+
+    #[test]
+    fn sync() {
+        let mut app = App::new();
+        app.add_plugin(Transform2dPlugin::default());
+        let entity = app
+            .world
+            .spawn((
+                TransformBundle2::from(Transform2::from_xy(1., 2.)),
+                SyncTransform2,
+            ))
+            .id();
+        app.update();
+        assert_eq!(transform(&app, entity).translation, Vec3::new(1., 2., 0.));
+
+        // written outside the 2D systems, like a physics step
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x = 5.;
+        app.update();
+        assert_eq!(transform2(&app, entity).translation, RealVec2::new(5., 2.));
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation(),
+            Vec3::new(5., 2., 0.)
+        );
+
+        app.world
+            .get_mut::<Transform2>(entity)
+            .unwrap()
+            .translation
+            .y = 3.;
+        app.update();
+        assert_eq!(transform(&app, entity).translation, Vec3::new(5., 3., 0.));
+
+        // both written, the most recent change wins
+        app.world
+            .get_mut::<Transform2>(entity)
+            .unwrap()
+            .translation
+            .y = 4.;
+        app.world.increment_change_tick();
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .y = 6.;
+        app.update();
+        assert_eq!(transform2(&app, entity).translation, RealVec2::new(5., 6.));
+
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .y = 7.;
+        app.world.increment_change_tick();
+        app.world
+            .get_mut::<Transform2>(entity)
+            .unwrap()
+            .translation
+            .y = 8.;
+        app.update();
+        assert_eq!(transform(&app, entity).translation, Vec3::new(5., 8., 0.));
+    }
+
+    #[test]
+    fn sync_with_transform_children() {
+        let mut app = App::new();
+        app.add_plugin(TransformPlugin)
+            .add_plugin(Transform2dPlugin::default())
+            .insert_resource(RenderOrigin2(RealVec2::new(100., 0.)));
+        let entity = app
+            .world
+            .spawn((
+                TransformBundle2::from(Transform2::from_xy(1., 2.)),
+                SyncTransform2,
+            ))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle::from(Transform::from_xyz(1., 0., 0.)))
+            .id();
+        app.world.entity_mut(entity).push_children(&[child]);
+        app.update();
+        app.update();
+        // relative to the render origin, not the absolute values Bevy propagates
+        assert_eq!(global_translation(&app, entity), Vec3::new(-99., 2., 0.));
+        assert_eq!(global_translation(&app, child), Vec3::new(-98., 2., 0.));
+
+        app.world.get_mut::<Transform>(child).unwrap().translation.x = 2.;
+        app.update();
+        assert_eq!(global_translation(&app, child), Vec3::new(-97., 2., 0.));
+
+        // rewritten with the same value, like a physics step with a body at rest, so only
+        // Bevy's propagation sees a change
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .set_changed();
+        app.update();
+        assert_eq!(global_translation(&app, entity), Vec3::new(-99., 2., 0.));
+        assert_eq!(global_translation(&app, child), Vec3::new(-97., 2., 0.));
+
+        app.world
+            .get_mut::<Transform2>(entity)
+            .unwrap()
+            .translation
+            .y = 3.;
+        app.update();
+        assert_eq!(global_translation(&app, entity), Vec3::new(-99., 3., 0.));
+        assert_eq!(global_translation(&app, child), Vec3::new(-97., 3., 0.));
+
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x = 5.;
+        app.update();
+        assert_eq!(global_translation(&app, entity), Vec3::new(-95., 3., 0.));
+        assert_eq!(global_translation(&app, child), Vec3::new(-93., 3., 0.));
+    }
+
+    #[test]
+    fn sync_keeps_flip_axis() {
+        let mut app = App::new();
+        app.add_plugin(Transform2dPlugin::default());
+        let entity = app
+            .world
+            .spawn((
+                TransformBundle2::from(Transform2 {
+                    flip_y: true,
+                    ..Transform2::from_xy(1., 2.).with_rotation(Rotation2::degrees(30.))
+                }),
+                SyncTransform2,
+            ))
+            .id();
+        let child = app
+            .world
+            .spawn(TransformBundle2::from(
+                Transform2::from_xy(1., 0.).with_rotation(Rotation2::degrees(10.)),
+            ))
+            .insert(PropagateTransform2::ALL - PropagateTransform2::FLIP)
+            .id();
+        app.world.entity_mut(entity).push_children(&[child]);
+        app.update();
+        let child_rotation = |app: &App| {
+            app.world
+                .get::<GlobalTransform2>(child)
+                .unwrap()
+                .rotation()
+                .as_degrees()
+        };
+        let before = child_rotation(&app);
+
+        // rewritten with the same value, like a physics step with a body at rest
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .set_changed();
+        app.update();
+        assert!(transform2(&app, entity).flip_y);
+        assert!((child_rotation(&app) - before).abs() < 0.001);
+
+        app.world
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x = 5.;
+        app.update();
+        let synced = transform2(&app, entity);
+        assert!(synced.flip_y && !synced.flip_x);
+        assert!((synced.rotation.as_degrees() - 30.).abs() < 0.001);
+        assert!((child_rotation(&app) - before).abs() < 0.001);
+    }
+}
@@ -1,15 +1,94 @@
 use super::*;
+use crate::hierarchy::report_hierarchy_errors;
+use crate::hierarchy::HierarchyError2;
+use crate::hierarchy::HierarchyErrorPolicy2;
+use crate::pivot::Pivot2;
+use crate::pixel_snap::PixelSnap2;
+use crate::precision::*;
+use crate::sync::SyncTransform2;
+use crate::transform2::InheritedPropagation2;
+use crate::transform2::PropagationWeights2;
+use bevy::ecs::query::QueryEntityError;
 use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Inserts the [`GlobalTransform2`] and [`GlobalTransform`] that entities with a [`Transform2`]
+/// need to be propagated and rendered, if they are missing.
+///
+/// Runs as an exclusive system so the components are in place before propagation in the same frame.
+/// [`PropagateTransform2`] isn't inserted, entities without one already use the rules from
+/// [`InheritedPropagation2`], which inserting the default would override.
+#[allow(clippy::type_complexity)]
+pub fn insert_missing_global_transforms_system(
+    world: &mut World,
+    query: &mut QueryState<
+        (Entity, Option<&GlobalTransform2>),
+        (
+            With<Transform2>,
+            Or<(Without<GlobalTransform2>, Without<GlobalTransform>)>,
+        ),
+    >,
+) {
+    let missing: Vec<(Entity, bool)> = query
+        .iter(world)
+        .map(|(entity, global_transform2)| (entity, global_transform2.is_some()))
+        .collect();
+    for (entity, has_global_transform2) in missing {
+        let mut entity = world.entity_mut(entity);
+        if has_global_transform2 {
+            // derive the new `GlobalTransform` from the existing `GlobalTransform2`
+            if let Some(mut global_transform2) = entity.get_mut::<GlobalTransform2>() {
+                global_transform2.set_changed();
+            }
+        } else {
+            entity.insert(GlobalTransform2::default());
+        }
+        if !entity.contains::<GlobalTransform>() {
+            entity.insert(GlobalTransform::default());
+        }
+    }
+}
+
+/// Warns about entities with both a [`Transform`] and a [`Transform2`], unless they have a [`SyncTransform2`].
+///
+/// Their [`GlobalTransform`] is left to Bevy's transform systems, so the [`Transform2`] has no effect
+/// on rendering.
+#[allow(clippy::type_complexity)]
+pub fn warn_transform_conflicts_system(
+    query: Query<
+        Entity,
+        (
+            With<Transform>,
+            With<Transform2>,
+            Without<SyncTransform2>,
+            Or<(Added<Transform>, Added<Transform2>)>,
+        ),
+    >,
+) {
+    for entity in query.iter() {
+        warn!(
+            "{entity:?} has both a Transform and a Transform2, its GlobalTransform won't be derived from the Transform2"
+        );
+    }
+}
 
 /// Update [`GlobalTransform2`] component of entities based on entity hierarchy and
 /// [`Transform2`] component.
-#[allow(clippy::type_complexity)]
+///
+/// Entities without a [`PropagateTransform2`] use the [`InheritedPropagation2`] of their
+/// nearest ancestor that has one, or [`PropagateTransform2::ALL`].
+///
+/// An entity is recomputed when any input to its [`GlobalTransform2`] changes, is added or is
+/// removed, along with all of its descendants.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn transform_2d_propagate_system(
     mut root_query: Query<
         (
             Option<(&Children, Changed<Children>)>,
+            Option<(&InheritedPropagation2, Changed<InheritedPropagation2>)>,
             &Transform2,
             Changed<Transform2>,
+            Option<(&Pivot2, Changed<Pivot2>)>,
             &mut GlobalTransform2,
             Entity,
         ),
@@ -18,87 +97,538 @@ pub fn transform_2d_propagate_system(
     mut transform_query: Query<(
         &Transform2,
         Changed<Transform2>,
+        Option<(&Pivot2, Changed<Pivot2>)>,
+        Option<(&PropagationWeights2, Changed<PropagationWeights2>)>,
         &mut GlobalTransform2,
-        &PropagateTransform2,
-        &Parent,
+        Option<(&PropagateTransform2, Changed<PropagateTransform2>)>,
+        (&Parent, Changed<Parent>),
     )>,
-    children_query: Query<(&Children, Changed<Children>), (With<Parent>, With<GlobalTransform2>)>,
+    children_query: Query<
+        (
+            &Children,
+            Changed<Children>,
+            Option<(&InheritedPropagation2, Changed<InheritedPropagation2>)>,
+        ),
+        (With<Parent>, With<GlobalTransform2>),
+    >,
+    removed_parents: RemovedComponents<Parent>,
+    removed_pivots: RemovedComponents<Pivot2>,
+    removed_weights: RemovedComponents<PropagationWeights2>,
+    removed_propagations: RemovedComponents<PropagateTransform2>,
+    removed_inherited: RemovedComponents<InheritedPropagation2>,
+    policy: Res<HierarchyErrorPolicy2>,
+    mut reported: Local<HashSet<HierarchyError2>>,
+    mut events: EventWriter<HierarchyError2>,
 ) {
-    for (children, transform_2d, transform_2d_changed, mut global_transform_2d, entity) in
-        root_query.iter_mut()
+    let removed = RemovedInputs {
+        transform: removed_parents
+            .iter()
+            .chain(removed_pivots.iter())
+            .chain(removed_weights.iter())
+            .chain(removed_propagations.iter())
+            .collect(),
+        inherited: removed_inherited.iter().collect(),
+    };
+    let mut errors = Vec::new();
+
+    for (
+        children,
+        inherited,
+        transform_2d,
+        transform_2d_changed,
+        pivot,
+        mut global_transform_2d,
+        entity,
+    ) in root_query.iter_mut()
     {
-        let mut changed = transform_2d_changed;
-        if transform_2d_changed {
-            *global_transform_2d = (*transform_2d).into();
+        let pivot_changed = pivot.is_some_and(|(_, changed)| changed);
+        let mut changed = transform_2d_changed
+            || pivot_changed
+            || global_transform_2d.is_added()
+            || removed.transform.contains(&entity);
+        if changed {
+            *global_transform_2d =
+                local_transform(transform_2d, pivot.map(|(pivot, _)| pivot)).into();
         }
 
         if let Some((children, changed_children)) = children {
-            changed |= changed_children;
+            changed |= changed_children
+                || inherited.is_some_and(|(_, changed)| changed)
+                || removed.inherited.contains(&entity);
+            let inherited =
+                inherited.map_or(PropagateTransform2::ALL, |(inherited, _)| inherited.0);
             for child in children {
-                let _ = propagate_recursive(
+                propagate_recursive(
                     &global_transform_2d,
                     &mut transform_query,
                     &children_query,
+                    &removed,
+                    &mut errors,
                     *child,
                     entity,
+                    inherited,
                     changed,
                 );
             }
         }
     }
+
+    report_hierarchy_errors(errors, *policy, &mut reported, &mut events);
 }
 
-#[allow(clippy::type_complexity)]
+/// Entities with a removed input to propagation.
+pub(crate) struct RemovedInputs {
+    /// Entities whose own [`GlobalTransform2`] is stale.
+    pub(crate) transform: HashSet<Entity>,
+    /// Entities whose descendants are stale because their default rules were removed.
+    pub(crate) inherited: HashSet<Entity>,
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn propagate_recursive(
     parent: &GlobalTransform2,
     transform_query: &mut Query<(
         &Transform2,
         Changed<Transform2>,
+        Option<(&Pivot2, Changed<Pivot2>)>,
+        Option<(&PropagationWeights2, Changed<PropagationWeights2>)>,
         &mut GlobalTransform2,
-        &PropagateTransform2,
-        &Parent,
+        Option<(&PropagateTransform2, Changed<PropagateTransform2>)>,
+        (&Parent, Changed<Parent>),
     )>,
-    children_query: &Query<(&Children, Changed<Children>), (With<Parent>, With<GlobalTransform2>)>,
+    children_query: &Query<
+        (
+            &Children,
+            Changed<Children>,
+            Option<(&InheritedPropagation2, Changed<InheritedPropagation2>)>,
+        ),
+        (With<Parent>, With<GlobalTransform2>),
+    >,
+    removed: &RemovedInputs,
+    errors: &mut Vec<HierarchyError2>,
     entity: Entity,
     expected_parent: Entity,
+    inherited: PropagateTransform2,
     mut changed: bool,
-) -> Result<(), ()> {
+) {
     let global_matrix = {
-        let (transform2, transform_changed, mut global_transform2, propagate, child_parent) =
-            transform_query.get_mut(entity).map_err(drop)?;
-        assert_eq!(
-            child_parent.get(), expected_parent,
-            "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
-        );
-        changed |= transform_changed;
+        let (
+            transform2,
+            transform_changed,
+            pivot,
+            weights,
+            mut global_transform2,
+            propagate,
+            (child_parent, parent_changed),
+        ) = match transform_query.get_mut(entity) {
+            Ok(item) => item,
+            Err(QueryEntityError::NoSuchEntity(_)) => {
+                errors.push(HierarchyError2::MissingEntity {
+                    entity,
+                    parent: expected_parent,
+                });
+                return;
+            }
+            // children that aren't part of the 2D hierarchy
+            Err(_) => return,
+        };
+        if child_parent.get() != expected_parent {
+            errors.push(HierarchyError2::ParentMismatch {
+                entity,
+                expected_parent,
+                actual_parent: child_parent.get(),
+            });
+            return;
+        }
+        changed |= transform_changed
+            || parent_changed
+            || pivot.is_some_and(|(_, changed)| changed)
+            || weights.is_some_and(|(_, changed)| changed)
+            || propagate.is_some_and(|(_, changed)| changed)
+            || global_transform2.is_added()
+            || removed.transform.contains(&entity);
         if changed {
-            *global_transform2 = parent.propagate_transform(*transform2, *propagate);
+            *global_transform2 = propagate_child(
+                parent,
+                local_transform(transform2, pivot.map(|(pivot, _)| pivot)),
+                propagate.map_or(inherited, |(propagate, _)| *propagate),
+                weights.map(|(weights, _)| weights),
+            );
         }
         *global_transform2
     };
-    let (children, changed_children) = children_query.get(entity).map_err(drop)?;
-    changed |= changed_children;
+    let Ok((children, changed_children, child_inherited)) = children_query.get(entity) else {
+        return;
+    };
+    changed |= changed_children
+        || child_inherited.is_some_and(|(_, changed)| changed)
+        || removed.inherited.contains(&entity);
+    let inherited = child_inherited.map_or(inherited, |(child_inherited, _)| child_inherited.0);
     for child in children {
-        let _ = propagate_recursive(
+        propagate_recursive(
             &global_matrix,
             transform_query,
             children_query,
+            removed,
+            errors,
             *child,
             entity,
+            inherited,
             changed,
         );
     }
-    Ok(())
 }
 
+/// The local transform that propagation combines with the parent's, with the [`Pivot2`] applied.
+#[inline]
+pub(crate) fn local_transform(transform2: &Transform2, pivot: Option<&Pivot2>) -> Transform2 {
+    pivot.map_or(*transform2, |pivot| pivot.apply(*transform2))
+}
+
+/// Computes the [`GlobalTransform2`] of a child of `parent` from its local transform,
+/// propagation rules and optional [`PropagationWeights2`].
+#[inline]
+pub(crate) fn propagate_child(
+    parent: &GlobalTransform2,
+    local: Transform2,
+    propagation: PropagateTransform2,
+    weights: Option<&PropagationWeights2>,
+) -> GlobalTransform2 {
+    match weights {
+        Some(weights) => parent
+            .weighted(weights)
+            .propagate_transform(local, propagation),
+        None => parent.propagate_transform(local, propagation),
+    }
+}
+
+/// The world space position that [`derive_global_transform`] makes the translations of
+/// render [`GlobalTransform`]s relative to.
+///
+/// With the `f64` feature this follows the active camera, so the `f32` translations used for
+/// rendering stay small and precise however far the camera is from the world origin.
+/// Otherwise it stays at the origin unless set by the user.
+///
+/// Only 2D entities and the [`Transform`] entities below them are rendered relative to it.
+/// Bevy's transform systems keep [`Transform`] roots and their descendants in absolute
+/// coordinates, so those are drawn offset by the origin and can't be mixed with the `f64` feature.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RenderOrigin2(pub RealVec2);
+
+/// Moves the [`RenderOrigin2`] to the active camera's [`GlobalTransform2`].
+pub fn update_render_origin_system(
+    mut render_origin: ResMut<RenderOrigin2>,
+    camera_query: Query<(&Camera, &GlobalTransform2)>,
+) {
+    if let Some((_, global_transform2)) = camera_query.iter().find(|(camera, _)| camera.is_active) {
+        let translation = global_transform2.translation();
+        if render_origin.0 != translation {
+            render_origin.0 = translation;
+        }
+    }
+}
+
+/// Derives the render [`GlobalTransform`] of 2D entities from their [`GlobalTransform2`],
+/// relative to the [`RenderOrigin2`]. Every entity is updated when the origin moves.
+///
+/// Entities with a [`Transform`] are left to Bevy's transform systems, unless they have a [`SyncTransform2`].
+/// Bevy's `transform_propagate_system` also writes the [`GlobalTransform`] of synced entities
+/// from their [`Transform`], so they're derived again every frame to overwrite it.
+///
+/// Entities with a [`PixelSnap2`] have their render transform snapped, and are derived again
+/// unsnapped when it's removed.
 #[allow(clippy::type_complexity)]
 pub fn derive_global_transform(
-    mut query: Query<
-        (&GlobalTransform2, &mut GlobalTransform),
-        (Changed<GlobalTransform2>, Without<Transform>),
-    >,
+    render_origin: Res<RenderOrigin2>,
+    removed_pixel_snaps: RemovedComponents<PixelSnap2>,
+    mut queries: ParamSet<(
+        Query<
+            (&GlobalTransform2, Option<&PixelSnap2>, &mut GlobalTransform),
+            Or<(Without<Transform>, With<SyncTransform2>)>,
+        >,
+        Query<
+            (&GlobalTransform2, Option<&PixelSnap2>, &mut GlobalTransform),
+            (
+                Or<(
+                    Changed<GlobalTransform2>,
+                    Changed<PixelSnap2>,
+                    With<SyncTransform2>,
+                )>,
+                Or<(Without<Transform>, With<SyncTransform2>)>,
+            ),
+        >,
+    )>,
 ) {
-    query.for_each_mut(|(global_transform_2d, mut global_transform)| {
-        *global_transform = (*global_transform_2d).into();
-    });
+    let origin = render_origin.0;
+    let derive = |(global_transform_2d, pixel_snap, mut global_transform): (
+        &GlobalTransform2,
+        Option<&PixelSnap2>,
+        Mut<GlobalTransform>,
+    )| {
+        let derived = match pixel_snap {
+            Some(pixel_snap) => pixel_snap.snap(global_transform_2d, origin),
+            None => global_transform_2d.to_render_transform(origin),
+        };
+        if *global_transform != derived {
+            *global_transform = derived;
+        }
+    };
+    if render_origin.is_changed() {
+        queries.p0().for_each_mut(derive);
+    } else {
+        queries.p1().for_each_mut(derive);
+        let mut query = queries.p0();
+        for entity in removed_pixel_snaps.iter() {
+            if let Ok(item) = query.get_mut(entity) {
+                derive(item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::assert_near;
+    use crate::test_util::translation;
+    use crate::Transform2dStage;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(Transform2dPlugin::default());
+        app
+    }
+
+    fn spawn(app: &mut App, transform2: Transform2) -> Entity {
+        app.world
+            .spawn((
+                transform2,
+                GlobalTransform2::default(),
+                GlobalTransform::default(),
+            ))
+            .id()
+    }
+
+    /// A parent rotated a quarter turn at `(10, 0)` with a child at `(1, 0)`.
+    fn hierarchy(app: &mut App) -> (Entity, Entity) {
+        let parent = spawn(
+            app,
+            Transform2::from_xy(10., 0.).with_rotation(Rotation2::degrees(90.)),
+        );
+        let child = spawn(app, Transform2::from_xy(1., 0.));
+        app.world.entity_mut(parent).push_children(&[child]);
+        app.update();
+        (parent, child)
+    }
+
+    #[test]
+    fn propagate_transform_changed() {
+        let mut app = app();
+        let (_, child) = hierarchy(&mut app);
+        assert_near(translation(&app, child), RealVec2::new(10., 1.));
+
+        app.world
+            .entity_mut(child)
+            .insert(PropagateTransform2::NOTHING);
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(1., 0.));
+
+        app.world.entity_mut(child).remove::<PropagateTransform2>();
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(10., 1.));
+    }
+
+    #[test]
+    fn parent_changed() {
+        let mut app = app();
+        let (_, child) = hierarchy(&mut app);
+        let other = spawn(&mut app, Transform2::from_xy(-5., 5.));
+        app.update();
+
+        app.world.entity_mut(other).push_children(&[child]);
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(-4., 5.));
+
+        app.world.entity_mut(other).remove_children(&[child]);
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(1., 0.));
+    }
+
+    #[test]
+    fn global_transform_inserted() {
+        let mut app = app();
+        let (parent, child) = hierarchy(&mut app);
+        for entity in [parent, child] {
+            let mut entity = app.world.entity_mut(entity);
+            entity.remove::<GlobalTransform2>();
+            entity.insert(GlobalTransform2::default());
+        }
+        app.update();
+        assert_near(translation(&app, parent), RealVec2::new(10., 0.));
+        assert_near(translation(&app, child), RealVec2::new(10., 1.));
+    }
+
+    #[test]
+    fn components_removed() {
+        let mut app = app();
+        let (parent, child) = hierarchy(&mut app);
+        app.world.entity_mut(parent).insert(Pivot2::new(1., 0.));
+        app.world
+            .entity_mut(child)
+            .insert(PropagationWeights2::splat(0.));
+        app.update();
+        assert_near(translation(&app, parent), RealVec2::new(11., -1.));
+        assert_near(translation(&app, child), RealVec2::new(1., 0.));
+
+        app.world.entity_mut(parent).remove::<Pivot2>();
+        app.world.entity_mut(child).remove::<PropagationWeights2>();
+        app.update();
+        assert_near(translation(&app, parent), RealVec2::new(10., 0.));
+        assert_near(translation(&app, child), RealVec2::new(10., 1.));
+
+        app.world
+            .entity_mut(parent)
+            .insert(InheritedPropagation2(PropagateTransform2::NOTHING));
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(1., 0.));
+
+        app.world
+            .entity_mut(parent)
+            .remove::<InheritedPropagation2>();
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(10., 1.));
+    }
+
+    fn hierarchy_errors(app: &App) -> Vec<HierarchyError2> {
+        let events = app.world.resource::<Events<HierarchyError2>>();
+        events.get_reader().iter(events).copied().collect()
+    }
+
+    #[test]
+    fn hierarchy_error_events() {
+        let mut app = app();
+        app.insert_resource(HierarchyErrorPolicy2::Event);
+        let (parent, child) = hierarchy(&mut app);
+        assert!(hierarchy_errors(&app).is_empty());
+
+        app.world.despawn(child);
+        app.update();
+        assert_eq!(
+            hierarchy_errors(&app),
+            [HierarchyError2::MissingEntity {
+                entity: child,
+                parent
+            }]
+        );
+
+        let mut app = self::app();
+        app.insert_resource(HierarchyErrorPolicy2::Event);
+        let (parent, child) = hierarchy(&mut app);
+        app.world.entity_mut(child).push_children(&[parent]);
+        app.update();
+        let errors = hierarchy_errors(&app);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            HierarchyError2::Cycle { entity } if entity == parent
+        ));
+    }
+
+    #[test]
+    fn hierarchy_error_log_once() {
+        let mut app = app();
+        app.insert_resource(HierarchyErrorPolicy2::LogOnce);
+        let (parent, child) = hierarchy(&mut app);
+        app.world.despawn(child);
+        app.update();
+        app.world
+            .entity_mut(parent)
+            .insert(Transform2::from_xy(3., 0.));
+        app.update();
+        assert_near(translation(&app, parent), RealVec2::new(3., 0.));
+    }
+
+    #[test]
+    fn dangling_child_default_policy() {
+        let mut app = app();
+        let (parent, child) = hierarchy(&mut app);
+        // `despawn` leaves the child in the parent's `Children`
+        app.world.despawn(child);
+        app.update();
+        app.world
+            .get_mut::<Transform2>(parent)
+            .unwrap()
+            .translation
+            .x = 3.;
+        app.update();
+        assert_near(translation(&app, parent), RealVec2::new(3., 0.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn hierarchy_error_panic() {
+        let mut app = app();
+        app.insert_resource(HierarchyErrorPolicy2::Panic);
+        let (_, child) = hierarchy(&mut app);
+        app.world.despawn(child);
+        app.update();
+    }
+
+    #[test]
+    fn insert_missing_global_transforms() {
+        let mut app = app();
+        let parent = app.world.spawn(Transform2::from_xy(10., 0.)).id();
+        let child = app.world.spawn(Transform2::from_xy(1., 0.)).id();
+        app.world.entity_mut(parent).push_children(&[child]);
+        app.update();
+        assert_near(translation(&app, child), RealVec2::new(11., 0.));
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation(),
+            Vec3::new(11., 0., 0.)
+        );
+
+        app.world.entity_mut(child).remove::<GlobalTransform>();
+        app.update();
+        assert_eq!(
+            app.world
+                .get::<GlobalTransform>(child)
+                .unwrap()
+                .translation(),
+            Vec3::new(11., 0., 0.)
+        );
+    }
+
+    #[test]
+    fn plugin_options() {
+        #[derive(Resource, Default)]
+        struct Seen(RealVec2);
+
+        let mut app = App::new();
+        app.add_plugin(
+            Transform2dPlugin::default()
+                .in_stage(CoreStage::PreUpdate)
+                .without_global_transform_derivation(),
+        )
+        .init_resource::<Seen>()
+        .add_system(
+            |query: Query<&GlobalTransform2, With<Parent>>, mut seen: ResMut<Seen>| {
+                for global_transform2 in query.iter() {
+                    seen.0 = global_transform2.translation();
+                }
+            },
+        );
+        assert_eq!(
+            app.world.resource::<Transform2dStage>().0,
+            CoreStage::PreUpdate.as_label()
+        );
+        let (_, child) = hierarchy(&mut app);
+        assert_near(app.world.resource::<Seen>().0, RealVec2::new(10., 1.));
+        assert_eq!(
+            *app.world.get::<GlobalTransform>(child).unwrap(),
+            GlobalTransform::default()
+        );
+    }
 }
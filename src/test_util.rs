@@ -0,0 +1,17 @@
+//! Helpers shared by the tests of the other modules.
+
+use crate::precision::RealVec2;
+use crate::transform2::GlobalTransform2;
+use bevy::prelude::*;
+
+/// The translation of the `GlobalTransform2` of `entity`.
+pub(crate) fn translation(app: &App, entity: Entity) -> RealVec2 {
+    app.world
+        .get::<GlobalTransform2>(entity)
+        .unwrap()
+        .translation()
+}
+
+pub(crate) fn assert_near(a: RealVec2, b: RealVec2) {
+    assert!((a - b).length() < 0.001, "{a} != {b}");
+}
@@ -1,12 +1,25 @@
-use bevy::math::vec2;
 use bevy::math::Affine3A;
 use bevy::math::Mat3A;
 use bevy::math::Vec3A;
 use bevy::prelude::*;
 
-#[derive(Component, Clone, Copy, Debug, Eq, PartialEq, Reflect)]
+use crate::precision::*;
+use crate::rotation2::Rotation2;
+
+/// Selects which properties of its parent's [`GlobalTransform2`] an entity inherits.
+///
+/// A set of flags, combined and tested with the usual bit operators or the named methods.
+/// Only the bits of the named flags are valid, [`PropagateTransform2::from_bits`] rejects
+/// any others. The flags currently name all eight bits of the `u8`, so values set through
+/// [`Reflect`] or deserialized are always valid.
+#[derive(Component, Clone, Copy, Eq, PartialEq, Hash, Reflect)]
 #[reflect(Component, Default, PartialEq)]
-pub struct PropagateTransform2(pub u8);
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
+pub struct PropagateTransform2(u8);
 
 impl Default for PropagateTransform2 {
     fn default() -> Self {
@@ -16,16 +29,141 @@ impl Default for PropagateTransform2 {
 
 impl PropagateTransform2 {
     pub const NOTHING: Self = Self(0);
-    pub const TRANSLATION: Self = Self(1);
+    /// Inherit the x coordinate of the translation.
+    pub const TRANSLATION_X: Self = Self(1);
+    /// Inherit the y coordinate of the translation.
+    pub const TRANSLATION_Y: Self = Self(32);
+    pub const TRANSLATION: Self = Self(33);
     pub const DEPTH: Self = Self(2);
     pub const ROTATION: Self = Self(4);
     pub const SCALE: Self = Self(8);
-    pub const ALL: Self = Self(15);
+    /// Inherit the parent's mirroring from [`Transform2::flip_x`] and [`Transform2::flip_y`].
+    pub const FLIP: Self = Self(16);
+    pub const ALL: Self = Self(63);
+    /// Modifies translation inheritance so the child's offset from the parent's world
+    /// position isn't rotated by the parent.
+    pub const TRANSLATION_UNROTATED: Self = Self(64);
+    /// Modifies translation inheritance so the child's offset from the parent's world
+    /// position isn't scaled, sheared or mirrored by the parent.
+    pub const TRANSLATION_UNSCALED: Self = Self(128);
+
+    /// Every valid bit.
+    const VALID_BITS: u8 = 255;
+
+    /// The named flags, composite flags first so that they're preferred when formatting.
+    const NAMED: [(&'static str, Self); 10] = [
+        ("ALL", Self::ALL),
+        ("TRANSLATION", Self::TRANSLATION),
+        ("TRANSLATION_X", Self::TRANSLATION_X),
+        ("TRANSLATION_Y", Self::TRANSLATION_Y),
+        ("DEPTH", Self::DEPTH),
+        ("ROTATION", Self::ROTATION),
+        ("SCALE", Self::SCALE),
+        ("FLIP", Self::FLIP),
+        ("TRANSLATION_UNROTATED", Self::TRANSLATION_UNROTATED),
+        ("TRANSLATION_UNSCALED", Self::TRANSLATION_UNSCALED),
+    ];
+
+    /// Creates a set of flags from its bits, or `None` if any bit doesn't belong to a named flag.
+    #[must_use]
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        if bits | Self::VALID_BITS == Self::VALID_BITS {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Creates a set of flags from its bits, discarding any bit that doesn't belong to a named flag.
+    #[must_use]
+    #[inline]
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Self(bits & Self::VALID_BITS)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
 
+    /// Returns true if any of the flags in `rule` are set.
     #[must_use]
     #[inline]
     pub const fn inherits(self, rule: Self) -> bool {
-        self.0 & rule.0 != 0
+        self.intersects(rule)
+    }
+
+    /// Returns true if all of the flags in `other` are set.
+    #[must_use]
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if any of the flags in `other` are set.
+    #[must_use]
+    #[inline]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if every property is inherited, see [`PropagateTransform2::ALL`].
+    #[must_use]
+    #[inline]
+    pub const fn is_all(self) -> bool {
+        self.contains(Self::ALL)
+    }
+
+    #[inline]
+    pub fn insert(&mut self, other: Self) {
+        *self |= other;
+    }
+
+    #[inline]
+    pub fn remove(&mut self, other: Self) {
+        *self -= other;
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, other: Self) {
+        *self ^= other;
+    }
+
+    /// Inserts the flags in `other` if `value` is true, otherwise removes them.
+    #[inline]
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.insert(other);
+        } else {
+            self.remove(other);
+        }
+    }
+
+    /// Iterates over the names and values of the flags that make up this set.
+    ///
+    /// A composite flag like `TRANSLATION` is yielded in place of its parts when all of them are set.
+    pub fn iter_names(self) -> impl Iterator<Item = (&'static str, Self)> {
+        let mut remaining = self;
+        Self::NAMED.into_iter().filter(move |(_, flag)| {
+            let yielded = self.contains(*flag) && remaining.intersects(*flag);
+            if yielded {
+                remaining -= *flag;
+            }
+            yielded
+        })
+    }
+
+    /// Iterates over the flags that make up this set, see [`PropagateTransform2::iter_names`].
+    pub fn iter(self) -> impl Iterator<Item = Self> {
+        self.iter_names().map(|(_, flag)| flag)
     }
 }
 
@@ -47,7 +185,6 @@ impl std::ops::BitOr for PropagateTransform2 {
     type Output = PropagateTransform2;
 
     #[inline]
-    #[must_use]
     fn bitor(self, rhs: Self) -> Self::Output {
         Self(self.0 | rhs.0)
     }
@@ -60,26 +197,227 @@ impl std::ops::BitOrAssign for PropagateTransform2 {
     }
 }
 
+impl std::ops::BitXor for PropagateTransform2 {
+    type Output = PropagateTransform2;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::BitXorAssign for PropagateTransform2 {
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
+impl std::ops::Sub for PropagateTransform2 {
+    type Output = PropagateTransform2;
+
+    /// The flags of `self` that aren't in `rhs`.
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+impl std::ops::SubAssign for PropagateTransform2 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+impl std::ops::Not for PropagateTransform2 {
+    type Output = PropagateTransform2;
+
+    /// The inherited properties that `self` doesn't inherit, so `!ROTATION` inherits everything
+    /// but the rotation.
+    ///
+    /// The complement is taken within [`PropagateTransform2::ALL`], the `TRANSLATION_UNROTATED`
+    /// and `TRANSLATION_UNSCALED` modifiers are never set in the result.
+    #[inline]
+    fn not(self) -> Self::Output {
+        Self(Self::ALL.0 & !self.0)
+    }
+}
+
+impl std::fmt::Display for PropagateTransform2 {
+    /// Formats the set as its flag names separated by `|`, like `TRANSLATION | SCALE`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return f.write_str("NOTHING");
+        }
+        for (i, (name, _)) in self.iter_names().enumerate() {
+            if 0 < i {
+                f.write_str(" | ")?;
+            }
+            f.write_str(name)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for PropagateTransform2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PropagateTransform2({self})")
+    }
+}
+
+/// The error returned when converting bits that don't all belong to named flags
+/// into a [`PropagateTransform2`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidPropagateTransform2Bits(pub u8);
+
+impl std::fmt::Display for InvalidPropagateTransform2Bits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid PropagateTransform2 bits: {:#x}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidPropagateTransform2Bits {}
+
+impl TryFrom<u8> for PropagateTransform2 {
+    type Error = InvalidPropagateTransform2Bits;
+
+    #[inline]
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        Self::from_bits(bits).ok_or(InvalidPropagateTransform2Bits(bits))
+    }
+}
+
+impl From<PropagateTransform2> for u8 {
+    #[inline]
+    fn from(propagation: PropagateTransform2) -> Self {
+        propagation.bits()
+    }
+}
+
+/// The default [`PropagateTransform2`] of every descendant of this entity that doesn't have one.
+///
+/// The nearest ancestor with an [`InheritedPropagation2`] wins. It doesn't affect the entity
+/// it's on, only its descendants. Descendants without a default propagate [`PropagateTransform2::ALL`].
+#[derive(Component, Clone, Copy, Debug, Default, Eq, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct InheritedPropagation2(pub PropagateTransform2);
+
+impl From<PropagateTransform2> for InheritedPropagation2 {
+    #[inline]
+    fn from(propagation: PropagateTransform2) -> Self {
+        Self(propagation)
+    }
+}
+
+/// Fractions of each property of the parent's [`GlobalTransform2`] that a child inherits,
+/// applied on top of its [`PropagateTransform2`] rules.
+///
+/// A background layer with a translation weight of `0.3` moves at 0.3 times the speed of its
+/// scrolling parent. Weights of `1` inherit the property fully and `0` not at all.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct PropagationWeights2 {
+    pub translation: RealVec2,
+    /// Scales the parent's rotation angle, which is in the range `(-PI, PI]`.
+    ///
+    /// Weights other than `0` and `1` make the inherited rotation jump when the parent's angle
+    /// wraps around at `PI`: with a weight of `0.5` a steadily spinning parent turns its child
+    /// to `+90°` and then straight to `-90°`. Use them for parents that stay within a half turn.
+    pub rotation: Real,
+    /// Interpolates between no scale and the parent's scale and shear.
+    pub scale: Real,
+    pub depth: Real,
+}
+
+impl PropagationWeights2 {
+    pub const ONE: Self = Self {
+        translation: RealVec2::ONE,
+        rotation: 1.,
+        scale: 1.,
+        depth: 1.,
+    };
+
+    /// Weights every property by `weight`.
+    #[inline]
+    #[must_use]
+    pub const fn splat(weight: Real) -> Self {
+        Self {
+            translation: RealVec2::splat(weight),
+            rotation: weight,
+            scale: weight,
+            depth: weight,
+        }
+    }
+
+    /// Weights only the translation, for parallax layers.
+    #[inline]
+    #[must_use]
+    pub const fn from_translation(translation: RealVec2) -> Self {
+        Self {
+            translation,
+            ..Self::ONE
+        }
+    }
+}
+
+impl Default for PropagationWeights2 {
+    fn default() -> Self {
+        Self::ONE
+    }
+}
+
+/// The type of [`Transform2::scale`].
+///
+/// A single uniform factor by default. With the `non_uniform_scale` feature enabled
+/// it is a [`RealVec2`] holding separate factors for the local x and y axes.
+#[cfg(not(feature = "non_uniform_scale"))]
+pub type Scale2 = Real;
+
+/// The type of [`Transform2::scale`].
+///
+/// A single uniform factor by default. With the `non_uniform_scale` feature enabled
+/// it is a [`RealVec2`] holding separate factors for the local x and y axes.
+#[cfg(feature = "non_uniform_scale")]
+pub type Scale2 = RealVec2;
+
 #[derive(Clone, Copy, Debug, PartialEq, Reflect, Component)]
 #[reflect(Component, Default, PartialEq)]
 pub struct Transform2 {
-    pub translation: Vec2,
-    pub depth: f32,
-    pub rotation: f32,
-    pub scale: f32,
+    pub translation: RealVec2,
+    pub depth: Real,
+    pub rotation: Rotation2,
+    pub scale: Scale2,
+    /// Mirrors the local x axis, before the scale and rotation are applied.
+    pub flip_x: bool,
+    /// Mirrors the local y axis, before the scale and rotation are applied.
+    pub flip_y: bool,
 }
 
 impl Transform2 {
+    #[cfg(not(feature = "non_uniform_scale"))]
     pub const IDENTITY: Self = Self {
-        translation: Vec2::ZERO,
+        translation: RealVec2::ZERO,
         depth: 0.0,
-        rotation: 0.0,
+        rotation: Rotation2::IDENTITY,
         scale: 1.0,
+        flip_x: false,
+        flip_y: false,
+    };
+
+    #[cfg(feature = "non_uniform_scale")]
+    pub const IDENTITY: Self = Self {
+        translation: RealVec2::ZERO,
+        depth: 0.0,
+        rotation: Rotation2::IDENTITY,
+        scale: RealVec2::ONE,
+        flip_x: false,
+        flip_y: false,
     };
 
     #[inline]
     #[must_use]
-    pub const fn from_translation(translation: Vec2) -> Self {
+    pub const fn from_translation(translation: RealVec2) -> Self {
         Self {
             translation,
             ..Self::IDENTITY
@@ -88,28 +426,28 @@ impl Transform2 {
 
     #[inline]
     #[must_use]
-    pub const fn from_translation3(translation: Vec3) -> Self {
+    pub const fn from_translation3(translation: RealVec3) -> Self {
         Self::from_xy(translation.x, translation.y).with_depth(translation.z)
     }
 
     #[inline]
     #[must_use]
-    pub const fn from_xy(x: f32, y: f32) -> Self {
+    pub const fn from_xy(x: Real, y: Real) -> Self {
         Self {
-            translation: Vec2 { x, y },
+            translation: RealVec2 { x, y },
             ..Self::IDENTITY
         }
     }
 
     #[inline]
     #[must_use]
-    pub const fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+    pub const fn from_xyz(x: Real, y: Real, z: Real) -> Self {
         Self::from_xy(x, y).with_depth(z)
     }
 
     #[inline]
     #[must_use]
-    pub const fn from_translation_z(translation: Vec2, z: f32) -> Self {
+    pub const fn from_translation_z(translation: RealVec2, z: Real) -> Self {
         Self {
             translation,
             depth: z,
@@ -119,16 +457,16 @@ impl Transform2 {
 
     #[inline]
     #[must_use]
-    pub const fn from_rotation(rotation: f32) -> Self {
+    pub fn from_rotation(rotation: impl Into<Rotation2>) -> Self {
         Self {
-            rotation,
+            rotation: rotation.into(),
             ..Self::IDENTITY
         }
     }
 
     #[inline]
     #[must_use]
-    pub const fn from_scale(scale: f32) -> Self {
+    pub const fn from_scale(scale: Scale2) -> Self {
         Self {
             scale,
             ..Self::IDENTITY
@@ -137,7 +475,7 @@ impl Transform2 {
 
     #[inline]
     #[must_use]
-    pub const fn from_depth(depth: f32) -> Self {
+    pub const fn from_depth(depth: Real) -> Self {
         Self {
             depth,
             ..Self::IDENTITY
@@ -146,130 +484,400 @@ impl Transform2 {
 
     #[inline]
     #[must_use]
-    pub const fn with_translation(mut self, translation: Vec2) -> Self {
+    pub const fn with_translation(mut self, translation: RealVec2) -> Self {
         self.translation = translation;
         self
     }
 
     #[inline]
     #[must_use]
-    pub const fn with_rotation(mut self, rotation: f32) -> Self {
-        self.rotation = rotation;
+    pub fn with_rotation(mut self, rotation: impl Into<Rotation2>) -> Self {
+        self.rotation = rotation.into();
         self
     }
 
     #[inline]
     #[must_use]
-    pub const fn with_depth(mut self, depth: f32) -> Self {
+    pub const fn with_depth(mut self, depth: Real) -> Self {
         self.depth = depth;
         self
     }
 
     #[inline]
     #[must_use]
-    pub const fn with_scale(mut self, scale: f32) -> Self {
+    pub const fn with_scale(mut self, scale: Scale2) -> Self {
         self.scale = scale;
         self
     }
 
     #[inline]
-    pub fn transform_point(&self, mut point: Vec2) -> Vec2 {
+    #[must_use]
+    pub const fn with_flip_x(mut self, flip_x: bool) -> Self {
+        self.flip_x = flip_x;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    #[inline]
+    pub fn transform_point(&self, mut point: RealVec2) -> RealVec2 {
         point = self.rotation_scale_matrix() * point;
         point += self.translation;
         point
     }
 
+    /// Get the scale factors along the local x and y axes.
+    #[inline]
+    #[must_use]
+    pub fn scale_xy(&self) -> RealVec2 {
+        #[cfg(not(feature = "non_uniform_scale"))]
+        return RealVec2::splat(self.scale);
+        #[cfg(feature = "non_uniform_scale")]
+        return self.scale;
+    }
+
+    /// Get the signs the local x and y axes are multiplied by, `-1` for a flipped axis and `1` otherwise.
+    #[inline]
+    #[must_use]
+    pub fn flip_xy(&self) -> RealVec2 {
+        RealVec2::new(
+            if self.flip_x { -1. } else { 1. },
+            if self.flip_y { -1. } else { 1. },
+        )
+    }
+
+    /// Returns true if exactly one axis is flipped, so that the transform is a mirror image.
+    #[inline]
+    #[must_use]
+    pub fn is_mirrored(&self) -> bool {
+        self.flip_x != self.flip_y
+    }
+
+    /// Returns true if the transform is a mirror image because only the y axis is flipped.
+    #[inline]
+    fn mirror_y(&self) -> bool {
+        self.flip_y && !self.flip_x
+    }
+
+    /// Projects `transform` like `From<Transform>`, but attributes a mirror image to the same
+    /// axis as the flip of `self`, so reading back a [`Transform`] written from `self` doesn't
+    /// turn a `flip_y` into a `flip_x` and a half turn.
+    #[inline]
+    pub(crate) fn projected_with_flip_axis_of(&self, transform: Transform) -> Self {
+        let mut global_transform2 = GlobalTransform2::from(GlobalTransform::from(transform));
+        global_transform2.mirror_y = self.mirror_y();
+        global_transform2.compute_transform()
+    }
+
+    /// The scale factors with the flips applied.
+    #[inline]
+    fn signed_scale_xy(&self) -> RealVec2 {
+        self.scale_xy() * self.flip_xy()
+    }
+
+    /// Multiplies `self` with `other`, treating `self` as the parent.
+    ///
+    /// With uniform scale the result is exact.
+    ///
+    /// With the `non_uniform_scale` feature, a parent that is scaled non-uniformly
+    /// and a child rotated relative to it combine into a shear, which a [`Transform2`]
+    /// can't represent. The translation of the result is still exact, but the rotations
+    /// are summed and the scales multiplied per axis, so the child keeps an unsheared
+    /// shape that is stretched along its own axes instead of the parent's.
+    /// The result is exact whenever the parent's scale is uniform or the child's rotation
+    /// is a multiple of 90 degrees.
+    ///
+    /// A mirrored parent reverses the direction of the child's rotation, and the flips of
+    /// both transforms combine.
     #[inline]
     #[must_use]
     pub fn mul_transform(&self, other: Self) -> Self {
         let translation = self.transform_point(other.translation);
         let depth = self.depth + other.depth;
-        let rotation = self.rotation + other.rotation;
+        let rotation = if self.is_mirrored() {
+            self.rotation * other.rotation.inverse()
+        } else {
+            self.rotation * other.rotation
+        };
         let scale = self.scale * other.scale;
         Self {
             translation,
             depth,
             rotation,
             scale,
+            flip_x: self.flip_x != other.flip_x,
+            flip_y: self.flip_y != other.flip_y,
         }
     }
 
+    /// Returns true if neither scale factor is zero, so that the transform can be inverted.
     #[inline]
-    pub fn rotate(&mut self, radians: f32) {
-        self.rotation += radians;
+    #[must_use]
+    pub fn is_invertible(&self) -> bool {
+        let scale = self.scale_xy();
+        scale.x != 0. && scale.y != 0.
     }
 
+    /// Computes the inverse of this transform.
+    ///
+    /// The result satisfies `self.mul_transform(self.inverse()) == Transform2::IDENTITY`
+    /// (up to floating point error). With the `non_uniform_scale` feature, the inverse of a
+    /// rotated, non-uniformly scaled transform contains a shear that a [`Transform2`] can't
+    /// represent, use [`Transform2::inverse_transform_point`] to map points exactly.
+    ///
+    /// If either scale factor is zero the result is not finite, see [`Transform2::try_inverse`].
     #[inline]
     #[must_use]
-    pub fn rotation_matrix(&self) -> Mat2 {
-        Mat2::from_angle(self.rotation)
+    pub fn inverse(&self) -> Self {
+        Self {
+            translation: -(self.rotation.inverse() * self.translation) / self.signed_scale_xy(),
+            depth: -self.depth,
+            rotation: if self.is_mirrored() {
+                self.rotation
+            } else {
+                self.rotation.inverse()
+            },
+            scale: self.scale.recip(),
+            flip_x: self.flip_x,
+            flip_y: self.flip_y,
+        }
     }
 
+    /// Computes the inverse of this transform, or `None` if either scale factor is zero.
     #[inline]
-    pub fn rotation_scale_matrix(&self) -> Mat2 {
+    #[must_use]
+    pub fn try_inverse(&self) -> Option<Self> {
+        self.is_invertible().then(|| self.inverse())
+    }
+
+    /// Transforms a `point` from the space this transform maps into back to its local space.
+    ///
+    /// This is the exact inverse of [`Transform2::transform_point`].
+    /// If either scale factor is zero the result is not finite.
+    #[inline]
+    #[must_use]
+    pub fn inverse_transform_point(&self, point: RealVec2) -> RealVec2 {
+        self.rotation.inverse() * (point - self.translation) / self.signed_scale_xy()
+    }
+
+    /// Linearly interpolates between `self` and `rhs` by `s`.
+    ///
+    /// The rotation follows the shortest arc, see [`Transform2::lerp_rotation`].
+    /// The flips switch from those of `self` to those of `rhs` halfway.
+    #[inline]
+    #[must_use]
+    pub fn lerp(&self, rhs: Self, s: Real) -> Self {
+        let flips = if s < 0.5 { self } else { &rhs };
+        Self {
+            translation: self.translation.lerp(rhs.translation, s),
+            depth: self.depth + (rhs.depth - self.depth) * s,
+            rotation: self.lerp_rotation(rhs, s),
+            scale: self.scale + (rhs.scale - self.scale) * s,
+            flip_x: flips.flip_x,
+            flip_y: flips.flip_y,
+        }
+    }
+
+    /// Interpolates between the rotations of `self` and `rhs` by `s` along the shortest arc.
+    #[inline]
+    #[must_use]
+    pub fn lerp_rotation(&self, rhs: Self, s: Real) -> Rotation2 {
+        self.rotation.slerp(rhs.rotation, s)
+    }
+
+    /// Moves `self` towards `target` with exponential smoothing.
+    ///
+    /// `rate` is how quickly the remaining distance shrinks, per second, and `dt` is the
+    /// elapsed time. Unlike a lerp by a fixed fraction the result doesn't depend on the frame rate.
+    #[inline]
+    #[must_use]
+    pub fn smooth_towards(&self, target: Self, rate: Real, dt: Real) -> Self {
+        self.lerp(target, smoothing_factor(rate, dt))
+    }
+
+    /// Rotates this [`Transform2`] by `rotation`, an angle in radians or a [`Rotation2`].
+    #[inline]
+    pub fn rotate(&mut self, rotation: impl Into<Rotation2>) {
+        self.rotation = rotation.into() * self.rotation;
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn rotation_matrix(&self) -> RealMat2 {
+        self.rotation.to_mat2()
+    }
+
+    #[inline]
+    pub fn rotation_scale_matrix(&self) -> RealMat2 {
         let (sin, cos) = self.rotation.sin_cos();
-        let sin = self.scale * sin;
-        let cos = self.scale * cos;
-        Mat2::from_cols(vec2(cos, sin), vec2(-sin, cos))
+        let scale = self.signed_scale_xy();
+        RealMat2::from_cols(
+            scale.x * RealVec2::new(cos, sin),
+            scale.y * RealVec2::new(-sin, cos),
+        )
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn to_affine2(&self) -> RealAffine2 {
+        RealAffine2::from_mat2_translation(self.rotation_scale_matrix(), self.translation)
     }
 
     #[inline]
     #[must_use]
     pub fn to_affine(&self) -> Affine3A {
-        let (sin, cos) = self.rotation.sin_cos();
-        let sin = self.scale * sin;
-        let cos = self.scale * cos;
-        Affine3A {
-            matrix3: Mat3A {
-                x_axis: Vec3A::new(cos, sin, 0.),
-                y_axis: Vec3A::new(-sin, cos, 0.),
-                z_axis: Vec3A::new(0., 0., 1.0),
-            },
-            translation: Vec3A::new(self.translation.x, self.translation.y, self.depth),
-        }
+        render_affine(self.rotation_scale_matrix(), self.translation, self.depth)
     }
 
     /// Get the unit vector in the local up direction.
     #[inline]
     #[must_use]
-    pub fn up(&self) -> Vec2 {
-        self.rotation_matrix() * Vec2::Y
+    pub fn up(&self) -> RealVec2 {
+        self.rotation_matrix() * RealVec2::Y
     }
 
     /// Get the unit vector in the local down direction.
     #[inline]
     #[must_use]
-    pub fn down(&self) -> Vec2 {
+    pub fn down(&self) -> RealVec2 {
         -self.up()
     }
 
     /// Get the unit vector in the local right direction.
     #[inline]
     #[must_use]
-    pub fn right(&self) -> Vec2 {
-        self.rotation_matrix() * Vec2::X
+    pub fn right(&self) -> RealVec2 {
+        self.rotation_matrix() * RealVec2::X
     }
 
     /// Get the unit vector in the local left direction.
     #[inline]
     #[must_use]
-    pub fn left(&self) -> Vec2 {
+    pub fn left(&self) -> RealVec2 {
         -self.right()
     }
 
+    /// The rotation that points the local `forward` axis along `direction`.
+    #[inline]
+    fn rotation_towards(&self, direction: RealVec2, forward: RealVec2) -> Rotation2 {
+        Rotation2::from_direction(direction)
+            * Rotation2::from_direction(self.signed_scale_xy() * forward).inverse()
+    }
+
+    /// Rotates this [`Transform2`] so that its local `forward` axis points at `target`.
+    ///
+    /// If this [`Transform2`] has a parent, `target` is relative to the [`Transform2`] of the parent,
+    /// see [`Transform2::look_at_world`] for a world space target.
+    /// Does nothing if `target` is equal to the translation.
+    #[inline]
+    pub fn look_at(&mut self, target: RealVec2, forward: RealVec2) {
+        let direction = target - self.translation;
+        if direction != RealVec2::ZERO {
+            self.rotation = self.rotation_towards(direction, forward);
+        }
+    }
+
+    /// Rotates this [`Transform2`] towards facing `target` with its local `forward` axis,
+    /// turning along the shortest arc by at most `max_radians`.
+    ///
+    /// If this [`Transform2`] has a parent, `target` is relative to the [`Transform2`] of the parent.
+    #[inline]
+    pub fn rotate_towards(&mut self, target: RealVec2, forward: RealVec2, max_radians: Real) {
+        let direction = target - self.translation;
+        if direction != RealVec2::ZERO {
+            let rotation = self.rotation_towards(direction, forward);
+            self.turn_towards(rotation, max_radians);
+        }
+    }
+
+    /// Moves the translation of this [`Transform2`] towards `target` by at most `max_distance`.
+    #[inline]
+    pub fn move_towards(&mut self, target: RealVec2, max_distance: Real) {
+        let offset = target - self.translation;
+        let distance = offset.length();
+        if distance <= max_distance {
+            self.translation = target;
+        } else {
+            self.translation += offset * (max_distance / distance);
+        }
+    }
+
+    /// Computes the local rotation that points the local `forward` axis at the world space
+    /// `target`, given the [`GlobalTransform2`] of the parent and the propagation rules.
+    ///
+    /// Returns `None` if the direction is undefined, because the entity is at `target` or the
+    /// rotation and scale it inherits from `parent` can't be inverted.
+    #[inline]
+    #[must_use]
+    pub fn world_look_rotation(
+        &self,
+        parent: &GlobalTransform2,
+        propagation: PropagateTransform2,
+        target: RealVec2,
+        forward: RealVec2,
+    ) -> Option<Rotation2> {
+        let world_direction = target - parent.propagate_transform(*self, propagation).translation();
+        let inherited = parent.inherited_matrix(propagation);
+        if world_direction == RealVec2::ZERO || inherited.determinant() == 0. {
+            return None;
+        }
+        Some(self.rotation_towards(inherited.inverse() * world_direction, forward))
+    }
+
+    /// Sets the local rotation of this [`Transform2`] so that its `forward` axis points at the
+    /// world space `target`, taking into account the rotation and scale it inherits from `parent`.
+    #[inline]
+    pub fn look_at_world(
+        &mut self,
+        parent: &GlobalTransform2,
+        propagation: PropagateTransform2,
+        target: RealVec2,
+        forward: RealVec2,
+    ) {
+        if let Some(rotation) = self.world_look_rotation(parent, propagation, target, forward) {
+            self.rotation = rotation;
+        }
+    }
+
+    /// Like [`Transform2::look_at_world`], but turns along the shortest arc by at most `max_radians`.
+    #[inline]
+    pub fn rotate_towards_world(
+        &mut self,
+        parent: &GlobalTransform2,
+        propagation: PropagateTransform2,
+        target: RealVec2,
+        forward: RealVec2,
+        max_radians: Real,
+    ) {
+        if let Some(rotation) = self.world_look_rotation(parent, propagation, target, forward) {
+            self.turn_towards(rotation, max_radians);
+        }
+    }
+
+    #[inline]
+    fn turn_towards(&mut self, rotation: Rotation2, max_radians: Real) {
+        self.rotation = self.rotation.rotate_towards(rotation, max_radians);
+    }
+
     /// Translates this [`Transform2`] around a `point` in space.
     ///
     /// If this [`Transform2`] has a parent, the `point` is relative to the [`Transform2`] of the parent.
     #[inline]
-    pub fn translate_around(&mut self, point: Vec2, angle: f32) {
-        self.translation = point + Mat2::from_angle(angle) * (self.translation - point);
+    pub fn translate_around(&mut self, point: RealVec2, rotation: impl Into<Rotation2>) {
+        self.translation = point + rotation.into() * (self.translation - point);
     }
 
     /// Rotates this [`Transform2`] around a `point` in space.
     ///
     /// If this [`Transform2`] has a parent, the `point` is relative to the [`Transform2`] of the parent.
     #[inline]
-    pub fn rotate_around(&mut self, point: Vec2, rotation: f32) {
+    pub fn rotate_around(&mut self, point: RealVec2, rotation: impl Into<Rotation2>) {
+        let rotation = rotation.into();
         self.translate_around(point, rotation);
         self.rotate(rotation);
     }
@@ -285,8 +893,14 @@ impl std::fmt::Display for Transform2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ T[{}, {}], Z[{}], R[{}], S[{}] }}",
-            self.translation.x, self.translation.y, self.depth, self.rotation, self.scale,
+            "{{ T[{}, {}], Z[{}], R[{}], S[{}], F[{}, {}] }}",
+            self.translation.x,
+            self.translation.y,
+            self.depth,
+            self.rotation,
+            self.scale,
+            self.flip_x,
+            self.flip_y,
         )
     }
 }
@@ -294,62 +908,525 @@ impl std::fmt::Display for Transform2 {
 impl From<Transform2> for Transform {
     fn from(transform_2: Transform2) -> Self {
         Self {
-            translation: transform_2.translation.extend(transform_2.depth),
-            rotation: Quat::from_rotation_z(transform_2.rotation),
-            scale: Vec2::splat(transform_2.scale).extend(1.0),
+            translation: vec2_to_f32(transform_2.translation)
+                .extend(real_to_f32(transform_2.depth)),
+            rotation: Quat::from_rotation_z(real_to_f32(transform_2.rotation.as_radians())),
+            scale: vec2_to_f32(transform_2.signed_scale_xy()).extend(1.0),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, Component)]
+/// Projects a [`Transform`] onto the XY plane, with the z translation as the depth.
+///
+/// Rotations out of the plane are discarded, and a mirror image is returned with
+/// [`Transform2::flip_x`] set, since a [`Transform`] doesn't say which axis was flipped.
+impl From<Transform> for Transform2 {
+    fn from(transform: Transform) -> Self {
+        GlobalTransform2::from(GlobalTransform::from(transform)).compute_transform()
+    }
+}
+
+/// Builds the `f32` affine transform used for rendering from a 2D linear map, translation and depth.
+#[inline]
+fn render_affine(matrix2: RealMat2, translation: RealVec2, depth: Real) -> Affine3A {
+    Affine3A {
+        matrix3: Mat3A {
+            x_axis: vec2_to_f32(matrix2.x_axis).extend(0.).into(),
+            y_axis: vec2_to_f32(matrix2.y_axis).extend(0.).into(),
+            z_axis: Vec3A::Z,
+        },
+        translation: vec2_to_f32(translation).extend(real_to_f32(depth)).into(),
+    }
+}
+
+/// The interpolation factor that exponential smoothing at `rate` per second covers in `dt` seconds.
+#[inline]
+fn smoothing_factor(rate: Real, dt: Real) -> Real {
+    1. - (-rate * dt).exp()
+}
+
+/// A linear map split into `rotation * scale_shear * flip`.
+struct Decomposed2 {
+    rotation: Rotation2,
+    rotation_matrix: RealMat2,
+    /// Upper triangular, with a positive diagonal unless a scale factor is zero.
+    scale_shear: RealMat2,
+    /// If the map is a mirror image, `flip` mirrors the x axis, or the y axis if `mirror_y` is set.
+    /// Otherwise it's the identity.
+    mirrored: bool,
+    mirror_y: bool,
+}
+
+impl Decomposed2 {
+    #[inline]
+    fn flip_matrix(&self) -> RealMat2 {
+        flip_matrix(self.mirrored, self.mirror_y)
+    }
+}
+
+#[inline]
+fn flip_matrix(mirrored: bool, mirror_y: bool) -> RealMat2 {
+    match (mirrored, mirror_y) {
+        (false, _) => RealMat2::IDENTITY,
+        (true, false) => RealMat2::from_diagonal(RealVec2::new(-1., 1.)),
+        (true, true) => RealMat2::from_diagonal(RealVec2::new(1., -1.)),
+    }
+}
+
+/// Splits a linear map into a rotation, the upper triangular scale and shear matrix left once
+/// the rotation is removed, and a flip if the map is a mirror image.
+///
+/// A flip of the y axis is a flip of the x axis and a half turn, so the matrix alone can't tell
+/// them apart. The flip mirrors the y axis if `mirror_y` is set, and the x axis otherwise.
+#[inline]
+fn decompose_rotation(matrix: RealMat2, mirror_y: bool) -> Decomposed2 {
+    let mirrored = matrix.determinant() < 0.;
+    let mirror_y = mirrored && mirror_y;
+    let unflipped = matrix * flip_matrix(mirrored, mirror_y);
+    let rotation = Rotation2::from_direction(unflipped.x_axis);
+    let rotation_matrix = rotation.to_mat2();
+    Decomposed2 {
+        rotation,
+        rotation_matrix,
+        scale_shear: rotation_matrix.transpose() * unflipped,
+        mirrored,
+        mirror_y,
+    }
+}
+
+/// The axis the mirroring of a child's global transform is attributed to, from the mirroring it
+/// inherits from its parent and its own.
+///
+/// A flip on the right of a rotation and scale can be moved past them, so each flip keeps its
+/// axis. Two flips cancel, or make a half turn if their axes differ.
+#[inline]
+fn combined_mirror_y(
+    inherited_mirrored: bool,
+    inherited_mirror_y: bool,
+    mirrored: bool,
+    mirror_y: bool,
+) -> bool {
+    match (inherited_mirrored, mirrored) {
+        (true, false) => inherited_mirror_y,
+        (false, true) => mirror_y,
+        _ => false,
+    }
+}
+
+/// Reads the scale factors off the diagonal of a matrix returned by [`decompose_rotation`].
+#[inline]
+fn scale_of(scale_shear: RealMat2) -> Scale2 {
+    #[cfg(not(feature = "non_uniform_scale"))]
+    return scale_shear.x_axis.x;
+    #[cfg(feature = "non_uniform_scale")]
+    return RealVec2::new(scale_shear.x_axis.x, scale_shear.y_axis.y);
+}
+
+/// The world space transform of an entity, computed by the propagation systems.
+///
+/// Stored as an [`RealAffine2`] plus a depth, so that combinations that can't be expressed
+/// as a [`Transform2`] (like the shear produced by a rotated child of a non-uniformly
+/// scaled parent) are represented exactly. The decomposed properties returned by
+/// [`GlobalTransform2::rotation`] and [`GlobalTransform2::scale`] are computed on demand.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Component)]
 #[reflect(Component, Default, PartialEq)]
-pub struct GlobalTransform2(Transform2);
+pub struct GlobalTransform2 {
+    affine: RealAffine2,
+    depth: Real,
+    /// If the transform is a mirror image, it's decomposed with a flip of the y axis instead
+    /// of the x axis. Set when the mirroring comes from a [`Transform2::flip_y`].
+    mirror_y: bool,
+}
+
+impl Default for GlobalTransform2 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
 
 impl GlobalTransform2 {
-    pub const IDENTITY: Self = Self(Transform2::IDENTITY);
+    pub const IDENTITY: Self = Self {
+        affine: RealAffine2::IDENTITY,
+        depth: 0.,
+        mirror_y: false,
+    };
+
+    /// A mirror image is decomposed as a flip of the x axis, see [`GlobalTransform2::compute_transform`].
+    #[must_use]
+    #[inline]
+    pub const fn from_affine(affine: RealAffine2, depth: Real) -> Self {
+        Self {
+            affine,
+            depth,
+            mirror_y: false,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn affine(&self) -> RealAffine2 {
+        self.affine
+    }
+
+    /// Decomposes this transform into a [`Transform2`].
+    ///
+    /// Any shear is discarded, so the result is only exact if the transform
+    /// can be represented by a [`Transform2`]. A mirror image is returned with
+    /// [`Transform2::flip_y`] set if its mirroring comes from a `flip_y`, and with
+    /// [`Transform2::flip_x`] set otherwise.
+    #[must_use]
+    #[inline]
+    pub fn compute_transform(&self) -> Transform2 {
+        let decomposed = self.decompose();
+        Transform2 {
+            translation: self.affine.translation,
+            depth: self.depth,
+            rotation: decomposed.rotation,
+            scale: scale_of(decomposed.scale_shear),
+            flip_x: decomposed.mirrored && !decomposed.mirror_y,
+            flip_y: decomposed.mirror_y,
+        }
+    }
+
+    #[inline]
+    fn decompose(&self) -> Decomposed2 {
+        decompose_rotation(self.affine.matrix2, self.mirror_y)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn translation(&self) -> RealVec2 {
+        self.affine.translation
+    }
+
+    /// The rotation of the transformed local x axis, or of its mirror image if
+    /// [`GlobalTransform2::is_mirrored`].
+    #[must_use]
+    #[inline]
+    pub fn rotation(&self) -> Rotation2 {
+        self.decompose().rotation
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn scale(&self) -> Scale2 {
+        scale_of(self.decompose().scale_shear)
+    }
+
+    /// Returns true if the transform is a mirror image, because an odd number of axes are flipped.
+    #[must_use]
+    #[inline]
+    pub fn is_mirrored(&self) -> bool {
+        self.affine.matrix2.determinant() < 0.
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn depth(&self) -> Real {
+        self.depth
+    }
+
+    /// Linearly interpolates between `self` and `rhs` by `s`.
+    ///
+    /// The rotation follows the shortest arc while the remaining scale and shear
+    /// are interpolated component-wise. Mirroring switches from `self` to `rhs` halfway.
+    #[must_use]
+    #[inline]
+    pub fn lerp(&self, rhs: Self, s: Real) -> Self {
+        let from = self.decompose();
+        let to = rhs.decompose();
+        let rotation = from.rotation.slerp(to.rotation, s).to_mat2();
+        let scale_shear = from.scale_shear + (to.scale_shear - from.scale_shear) * s;
+        let flipped = if s < 0.5 { &from } else { &to };
+        Self {
+            affine: RealAffine2::from_mat2_translation(
+                rotation * scale_shear * flipped.flip_matrix(),
+                self.translation().lerp(rhs.translation(), s),
+            ),
+            depth: self.depth + (rhs.depth - self.depth) * s,
+            mirror_y: flipped.mirror_y,
+        }
+    }
 
+    /// Interpolates between the rotations of `self` and `rhs` by `s` along the shortest arc.
     #[must_use]
     #[inline]
-    pub fn transform(&self) -> &Transform2 {
-        &self.0
+    pub fn lerp_rotation(&self, rhs: Self, s: Real) -> Rotation2 {
+        self.rotation().slerp(rhs.rotation(), s)
     }
 
+    /// Moves `self` towards `target` with exponential smoothing, see [`Transform2::smooth_towards`].
     #[must_use]
     #[inline]
-    pub fn translation(&self) -> Vec2 {
-        self.transform().translation
+    pub fn smooth_towards(&self, target: Self, rate: Real, dt: Real) -> Self {
+        self.lerp(target, smoothing_factor(rate, dt))
     }
 
     #[must_use]
     #[inline]
-    pub fn rotation(&self) -> f32 {
-        self.transform().rotation
+    pub fn mul_transform(&self, transform: Transform2) -> Self {
+        Self {
+            affine: self.affine * transform.to_affine2(),
+            depth: self.depth + transform.depth,
+            mirror_y: combined_mirror_y(
+                self.is_mirrored(),
+                self.mirror_y,
+                transform.is_mirrored(),
+                transform.mirror_y(),
+            ),
+        }
     }
 
     #[must_use]
     #[inline]
-    pub fn scale(&self) -> f32 {
-        self.transform().scale
+    pub fn mul_vec2(&self, value: RealVec2) -> RealVec2 {
+        self.affine.transform_point2(value)
     }
 
+    /// Returns true if the transform can be inverted, which is the case unless a scale factor is zero.
     #[must_use]
     #[inline]
-    pub fn depth(&self) -> f32 {
-        self.transform().depth
+    pub fn is_invertible(&self) -> bool {
+        self.affine.matrix2.determinant() != 0.
     }
 
+    /// Converts a world space `point` into the local space of this entity.
+    ///
+    /// This is the inverse of [`GlobalTransform2::mul_vec2`].
+    /// If either scale factor is zero the result is not finite.
     #[must_use]
     #[inline]
-    pub fn mul_transform(&self, transform: Transform2) -> Transform2 {
-        self.0.mul_transform(transform)
+    pub fn inverse_mul_vec2(&self, point: RealVec2) -> RealVec2 {
+        self.affine.inverse().transform_point2(point)
     }
 
+    /// Computes the inverse of this transform.
+    ///
+    /// If either scale factor is zero the result is not finite, see [`GlobalTransform2::try_inverse`].
     #[must_use]
     #[inline]
-    pub fn mul_vec2(&self, value: Vec2) -> Vec2 {
-        self.0.transform_point(value)
+    pub fn inverse(&self) -> Self {
+        Self {
+            affine: self.affine.inverse(),
+            depth: -self.depth,
+            mirror_y: self.mirror_y,
+        }
     }
 
+    /// Computes the inverse of this transform, or `None` if either scale factor is zero.
+    #[must_use]
+    #[inline]
+    pub fn try_inverse(&self) -> Option<Self> {
+        self.is_invertible().then(|| self.inverse())
+    }
+
+    /// Computes the transform a child with [`PropagationWeights2`] inherits from, with each
+    /// property of this transform scaled by its weight. Mirroring isn't weighted.
+    ///
+    /// The rotation weight scales the wrapped angle, so fractional weights are discontinuous
+    /// where the angle wraps at `PI`, see [`PropagationWeights2::rotation`].
+    #[must_use]
+    #[inline]
+    pub fn weighted(&self, weights: &PropagationWeights2) -> Self {
+        if *weights == PropagationWeights2::ONE {
+            return *self;
+        }
+        let decomposed = self.decompose();
+        let rotation = Rotation2::radians(decomposed.rotation.as_radians() * weights.rotation);
+        let scale_shear =
+            RealMat2::IDENTITY + (decomposed.scale_shear - RealMat2::IDENTITY) * weights.scale;
+        Self {
+            affine: RealAffine2::from_mat2_translation(
+                rotation.to_mat2() * scale_shear * decomposed.flip_matrix(),
+                self.affine.translation * weights.translation,
+            ),
+            depth: self.depth * weights.depth,
+            mirror_y: self.mirror_y,
+        }
+    }
+
+    /// Computes the local translation a child of this entity needs, so that its
+    /// global translation is the world space `point` under the `propagation` rules.
+    ///
+    /// Returns `None` if the inherited part of the translation can't be solved for,
+    /// because the map applied to the offset can't be inverted or maps the needed axis to zero.
+    #[must_use]
+    #[inline]
+    pub fn local_translation_of(
+        &self,
+        point: RealVec2,
+        propagation: PropagateTransform2,
+    ) -> Option<RealVec2> {
+        let inherits_x = propagation.inherits(PropagateTransform2::TRANSLATION_X);
+        let inherits_y = propagation.inherits(PropagateTransform2::TRANSLATION_Y);
+        let matrix2 = self.translation_matrix(propagation);
+        let offset = point - self.affine.translation;
+        match (inherits_x, inherits_y) {
+            (true, true) => (matrix2.determinant() != 0.).then(|| matrix2.inverse() * offset),
+            (false, false) => Some(point),
+            // the local y is kept, solve the first row of the map for x
+            (true, false) => (matrix2.x_axis.x != 0.).then(|| {
+                RealVec2::new(
+                    (offset.x - matrix2.y_axis.x * point.y) / matrix2.x_axis.x,
+                    point.y,
+                )
+            }),
+            (false, true) => (matrix2.y_axis.y != 0.).then(|| {
+                RealVec2::new(
+                    point.x,
+                    (offset.y - matrix2.x_axis.y * point.x) / matrix2.y_axis.y,
+                )
+            }),
+        }
+    }
+
+    /// Computes the local [`Transform2`] that places a child of `parent` at this global
+    /// transform under the `propagation` rules.
+    ///
+    /// This is the inverse of propagation, `parent` propagating the result with `propagation`
+    /// gives back `self` unless `self` has a shear relative to `parent`, which is discarded.
+    /// Returns `None` if an inherited property of `parent` can't be inverted because its scale is zero.
+    #[must_use]
+    #[inline]
+    pub fn reparented_to(
+        &self,
+        parent: &GlobalTransform2,
+        propagation: PropagateTransform2,
+    ) -> Option<Transform2> {
+        let inherited = parent.inherited_matrix(propagation);
+        if inherited.determinant() == 0. {
+            return None;
+        }
+        // if the child's flip cancels an inherited one its axis is lost, and the x axis is used
+        let inherited_mirrored =
+            propagation.inherits(PropagateTransform2::FLIP) && parent.is_mirrored();
+        let mirror_y = !inherited_mirrored && self.mirror_y;
+        let decomposed = decompose_rotation(inherited.inverse() * self.affine.matrix2, mirror_y);
+        Some(Transform2 {
+            translation: parent.local_translation_of(self.translation(), propagation)?,
+            depth: if propagation.inherits(PropagateTransform2::DEPTH) {
+                self.depth - parent.depth
+            } else {
+                self.depth
+            },
+            rotation: decomposed.rotation,
+            scale: scale_of(decomposed.scale_shear),
+            flip_x: decomposed.mirrored && !decomposed.mirror_y,
+            flip_y: decomposed.mirror_y,
+        })
+    }
+
+    /// Converts this transform to the `f32` [`GlobalTransform`] used for rendering, with the
+    /// translation made relative to `origin` before the conversion so that it keeps its precision.
+    #[must_use]
+    #[inline]
+    pub fn to_render_transform(&self, origin: RealVec2) -> GlobalTransform {
+        render_affine(
+            self.affine.matrix2,
+            self.affine.translation - origin,
+            self.depth,
+        )
+        .into()
+    }
+
+    /// Projects a render [`GlobalTransform`] onto the XY plane, the inverse of [`Self::to_render_transform`]
+    /// for transforms without any rotation out of the plane.
+    ///
+    /// The z translation becomes the depth and the z axis is discarded.
+    #[must_use]
+    #[inline]
+    pub fn from_render_transform(global_transform: &GlobalTransform, origin: RealVec2) -> Self {
+        let affine = global_transform.affine();
+        Self {
+            affine: RealAffine2::from_mat2_translation(
+                RealMat2::from_cols(
+                    vec2_from_f32(affine.matrix3.x_axis.truncate()),
+                    vec2_from_f32(affine.matrix3.y_axis.truncate()),
+                ),
+                vec2_from_f32(affine.translation.truncate()) + origin,
+            ),
+            depth: real_from_f32(affine.translation.z),
+            mirror_y: false,
+        }
+    }
+
+    /// The part of this transform's linear map that a child inherits under the `propagation` rules.
+    ///
+    /// The matrix is split into a rotation, the scale and shear that remain without it and
+    /// a mirroring flip, `ROTATION` inherits the first, `SCALE` the second and `FLIP` the third.
+    #[must_use]
+    #[inline]
+    pub(crate) fn inherited_matrix(&self, propagation: PropagateTransform2) -> RealMat2 {
+        let linear =
+            PropagateTransform2::ROTATION | PropagateTransform2::SCALE | PropagateTransform2::FLIP;
+        let inherited = propagation & linear;
+        if inherited == linear {
+            return self.affine.matrix2;
+        }
+        if inherited == PropagateTransform2::NOTHING {
+            return RealMat2::IDENTITY;
+        }
+        let decomposed = self.decompose();
+        let mut matrix = RealMat2::IDENTITY;
+        if propagation.inherits(PropagateTransform2::ROTATION) {
+            matrix = decomposed.rotation_matrix;
+        }
+        if propagation.inherits(PropagateTransform2::SCALE) {
+            matrix *= decomposed.scale_shear;
+        }
+        if propagation.inherits(PropagateTransform2::FLIP) {
+            matrix *= decomposed.flip_matrix();
+        }
+        matrix
+    }
+
+    /// The linear map applied to a child's translation before it's offset by this transform's,
+    /// leaving out the rotation or scale if `TRANSLATION_UNROTATED` or `TRANSLATION_UNSCALED` is set.
+    #[must_use]
+    #[inline]
+    fn translation_matrix(&self, propagation: PropagateTransform2) -> RealMat2 {
+        let unrotated = propagation.inherits(PropagateTransform2::TRANSLATION_UNROTATED);
+        let unscaled = propagation.inherits(PropagateTransform2::TRANSLATION_UNSCALED);
+        match (unrotated, unscaled) {
+            (false, false) => self.affine.matrix2,
+            (true, true) => RealMat2::IDENTITY,
+            (true, false) => {
+                let decomposed = self.decompose();
+                decomposed.scale_shear * decomposed.flip_matrix()
+            }
+            (false, true) => self.decompose().rotation_matrix,
+        }
+    }
+
+    /// The global translation of a child with the local `translation`, taking each
+    /// coordinate from the parent's map only if that axis is inherited.
+    #[must_use]
+    #[inline]
+    fn inherited_translation(
+        &self,
+        translation: RealVec2,
+        propagation: PropagateTransform2,
+    ) -> RealVec2 {
+        if !propagation.inherits(PropagateTransform2::TRANSLATION) {
+            return translation;
+        }
+        let mapped = self.affine.translation + self.translation_matrix(propagation) * translation;
+        RealVec2::new(
+            if propagation.inherits(PropagateTransform2::TRANSLATION_X) {
+                mapped.x
+            } else {
+                translation.x
+            },
+            if propagation.inherits(PropagateTransform2::TRANSLATION_Y) {
+                mapped.y
+            } else {
+                translation.y
+            },
+        )
+    }
+
+    /// Computes the [`GlobalTransform2`] of a child with the local transform `other`,
+    /// inheriting only the properties of `self` selected by `propagation`.
     #[inline]
     #[must_use]
     pub(crate) fn propagate_transform(
@@ -358,51 +1435,56 @@ impl GlobalTransform2 {
         propagation: PropagateTransform2,
     ) -> Self {
         if propagation == PropagateTransform2::ALL {
-            Self(self.mul_transform(other))
+            self.mul_transform(other)
         } else {
-            Self(Transform2 {
-                translation: if propagation.inherits(PropagateTransform2::TRANSLATION) {
-                    self.mul_vec2(other.translation)
-                } else {
-                    other.translation
-                },
+            let matrix2 = self.inherited_matrix(propagation) * other.rotation_scale_matrix();
+            let translation = self.inherited_translation(other.translation, propagation);
+            Self {
+                affine: RealAffine2::from_mat2_translation(matrix2, translation),
                 depth: if propagation.inherits(PropagateTransform2::DEPTH) {
-                    self.depth() + other.depth
+                    self.depth + other.depth
                 } else {
                     other.depth
                 },
-                rotation: if propagation.inherits(PropagateTransform2::ROTATION) {
-                    self.rotation() + other.rotation
-                } else {
-                    other.rotation
-                },
-                scale: if propagation.inherits(PropagateTransform2::SCALE) {
-                    self.scale() * other.scale
-                } else {
-                    other.scale
-                },
-            })
+                mirror_y: combined_mirror_y(
+                    propagation.inherits(PropagateTransform2::FLIP) && self.is_mirrored(),
+                    self.mirror_y,
+                    other.is_mirrored(),
+                    other.mirror_y(),
+                ),
+            }
         }
     }
 }
 
 impl std::fmt::Display for GlobalTransform2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+        self.compute_transform().fmt(f)
     }
 }
 
 impl From<Transform2> for GlobalTransform2 {
     #[inline]
     fn from(transform2d: Transform2) -> Self {
-        GlobalTransform2(transform2d)
+        Self {
+            affine: transform2d.to_affine2(),
+            depth: transform2d.depth,
+            mirror_y: transform2d.mirror_y(),
+        }
     }
 }
 
 impl From<GlobalTransform2> for GlobalTransform {
     #[inline]
     fn from(global_transform_2: GlobalTransform2) -> Self {
-        global_transform_2.0.into()
+        global_transform_2.to_render_transform(RealVec2::ZERO)
+    }
+}
+
+impl From<GlobalTransform> for GlobalTransform2 {
+    #[inline]
+    fn from(global_transform: GlobalTransform) -> Self {
+        Self::from_render_transform(&global_transform, RealVec2::ZERO)
     }
 }
 
@@ -415,17 +1497,32 @@ impl From<Transform2> for GlobalTransform {
 
 #[cfg(test)]
 mod test {
-    use bevy::math::vec2;
-
     use super::*;
+    #[cfg(not(feature = "f64"))]
+    use std::f32::consts::PI;
+    #[cfg(not(feature = "f64"))]
+    use std::f32::consts::TAU;
+    #[cfg(feature = "f64")]
+    use std::f64::consts::PI;
+    #[cfg(feature = "f64")]
+    use std::f64::consts::TAU;
+
+    fn vec2(x: Real, y: Real) -> RealVec2 {
+        RealVec2::new(x, y)
+    }
 
     #[test]
     fn transform2_vs_transform() {
+        #[cfg(not(feature = "non_uniform_scale"))]
+        let scale = 4.;
+        #[cfg(feature = "non_uniform_scale")]
+        let scale = RealVec2::splat(4.);
         let transform2 = Transform2 {
             translation: (1., 2.).into(),
             depth: 5.,
-            rotation: 2.,
-            scale: 4.,
+            rotation: Rotation2::radians(2.),
+            scale,
+            ..Default::default()
         };
         let transform = Transform::from(transform2);
 
@@ -441,8 +1538,8 @@ mod test {
         let e = 0.001;
 
         for &v in &vs {
-            let a = transform2.transform_point(v);
-            let b = transform.transform_point(v.extend(0.0));
+            let a = vec2_to_f32(transform2.transform_point(v));
+            let b = transform.transform_point(vec2_to_f32(v).extend(0.0));
             assert!((a.x - b.x).abs() < e);
             assert!((a.y - b.y).abs() < e);
         }
@@ -451,8 +1548,8 @@ mod test {
         let g = transform.mul_transform(transform);
 
         for &v in &vs {
-            let a = f.transform_point(v);
-            let b = g.transform_point(v.extend(0.0));
+            let a = vec2_to_f32(f.transform_point(v));
+            let b = g.transform_point(vec2_to_f32(v).extend(0.0));
             assert!((a.x - b.x).abs() < e);
             assert!((a.y - b.y).abs() < e);
         }
@@ -465,6 +1562,7 @@ mod test {
         println!("aff3: {:#?}", j.affine());
 
         for &v in &vs {
+            let v = vec2_to_f32(v);
             let a = i.transform_point(v.extend(0.0));
             let b = j.transform_point(v.extend(0.0));
             assert!((a.x - b.x).abs() < e);
@@ -472,21 +1570,416 @@ mod test {
         }
     }
 
+    #[cfg(feature = "non_uniform_scale")]
+    #[test]
+    fn non_uniform_scale_vs_transform() {
+        let parent = Transform2 {
+            translation: (1., 2.).into(),
+            depth: 5.,
+            rotation: Rotation2::radians(0.7),
+            scale: vec2(3., -0.5),
+            ..Default::default()
+        };
+        let child = Transform2::from_xy(4., -3.).with_scale(vec2(0.25, 2.));
+        let e = 0.001;
+
+        let a = parent.mul_transform(child);
+        let b = Transform::from(parent).mul_transform(Transform::from(child));
+        for v in [vec2(10., -1.), vec2(0., 3.), vec2(-2., -7.)] {
+            let p = vec2_to_f32(a.transform_point(v));
+            let q = b.transform_point(vec2_to_f32(v).extend(0.));
+            assert!((p.x - q.x).abs() < e);
+            assert!((p.y - q.y).abs() < e);
+        }
+
+        let child = child.with_rotation(1.);
+        let propagated =
+            GlobalTransform2::from(parent).propagate_transform(child, PropagateTransform2::ALL);
+        let expected = GlobalTransform::from(parent).mul_transform(Transform::from(child));
+        let derived = GlobalTransform::from(propagated);
+        for v in [vec2(10., -1.), vec2(0., 3.), vec2(-2., -7.)] {
+            let p = vec2_to_f32(propagated.mul_vec2(v));
+            let q = expected.transform_point(vec2_to_f32(v).extend(0.));
+            let r = derived.transform_point(vec2_to_f32(v).extend(0.));
+            assert!((p - q.truncate()).length() < e);
+            assert!((q - r).length() < e);
+        }
+    }
+
+    #[test]
+    fn compute_transform() {
+        let e = 0.001;
+        let transform2 = Transform2::from_xyz(3., -7., 2.)
+            .with_rotation(-2.5)
+            .with_scale(Transform2::IDENTITY.scale * 1.5);
+        let computed = GlobalTransform2::from(transform2).compute_transform();
+        assert!((computed.translation - transform2.translation).length() < e);
+        assert!((computed.depth - transform2.depth).abs() < e);
+        assert!(computed.rotation.angle_to(transform2.rotation).abs() < e);
+        assert!((computed.scale_xy() - transform2.scale_xy()).length() < e);
+    }
+
+    #[test]
+    fn to_render_transform() {
+        let origin = vec2(100_000., -50_000.);
+        let global_transform2 = GlobalTransform2::from(
+            Transform2::from_translation(origin + vec2(0.25, 0.5))
+                .with_depth(3.)
+                .with_rotation(1.),
+        );
+        let render_transform = global_transform2.to_render_transform(origin);
+        assert_eq!(render_transform.translation(), Vec3::new(0.25, 0.5, 3.));
+        assert!(render_transform
+            .compute_transform()
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(1.), 0.001));
+        let round_trip = GlobalTransform2::from_render_transform(&render_transform, origin);
+        assert!(round_trip
+            .affine()
+            .abs_diff_eq(global_transform2.affine(), 0.001));
+        assert_eq!(round_trip.depth(), 3.);
+    }
+
+    #[test]
+    fn inverse() {
+        let e = 0.001;
+        let transform2 = Transform2::from_xyz(3., -7., 2.)
+            .with_rotation(1.3)
+            .with_scale(Transform2::IDENTITY.scale * 2.5);
+        let identity = transform2.mul_transform(transform2.inverse());
+        assert!(identity.translation.length() < e);
+        assert!(identity.rotation.as_radians().abs() < e);
+        assert!(identity.depth.abs() < e);
+        assert!((identity.scale_xy() - RealVec2::ONE).length() < e);
+
+        for v in [vec2(10., -1.), vec2(0., 3.), vec2(-2., -7.)] {
+            let w = transform2.inverse_transform_point(transform2.transform_point(v));
+            assert!((w - v).length() < e);
+        }
+
+        let zero = Transform2::from_scale(Transform2::IDENTITY.scale * 0.);
+        assert!(zero.try_inverse().is_none());
+        assert!(GlobalTransform2::from(zero).try_inverse().is_none());
+    }
+
+    #[test]
+    fn reparented_to() {
+        let e = 0.001;
+        let parent = GlobalTransform2::from(
+            Transform2::from_xyz(-4., 9., 1.)
+                .with_rotation(-0.4)
+                .with_scale(Transform2::IDENTITY.scale * 3.)
+                .with_flip_y(true),
+        );
+        let child = Transform2::from_xyz(2., 5., 3.)
+            .with_rotation(0.9)
+            .with_flip_x(true);
+        for i in 0..=255 {
+            let propagation = PropagateTransform2::from_bits(i).unwrap();
+            let global = parent.propagate_transform(child, propagation);
+            let local = global.reparented_to(&parent, propagation).unwrap();
+            assert!((local.translation - child.translation).length() < e);
+            assert!((local.depth - child.depth).abs() < e);
+            assert!(local.rotation.angle_to(child.rotation).abs() < e);
+            assert!((local.scale_xy() - child.scale_xy()).length() < e);
+            assert_eq!(local.flip_xy(), child.flip_xy());
+        }
+
+        let zero = GlobalTransform2::from(Transform2::from_scale(Transform2::IDENTITY.scale * 0.));
+        assert!(parent
+            .reparented_to(&zero, PropagateTransform2::TRANSLATION)
+            .is_none());
+        assert!(parent
+            .reparented_to(&zero, PropagateTransform2::ROTATION)
+            .is_some());
+    }
+
+    #[test]
+    fn flip() {
+        let e = 0.001;
+        let parent = Transform2::from_xy(3., -1.)
+            .with_rotation(0.6)
+            .with_scale(Transform2::IDENTITY.scale * 2.)
+            .with_flip_x(true);
+        let child = Transform2::from_xy(4., 1.).with_rotation(0.3);
+        let vs = [vec2(10., -1.), vec2(0., 3.), vec2(-2., -7.)];
+
+        let transform = Transform::from(parent);
+        for v in vs {
+            let a = vec2_to_f32(parent.transform_point(v));
+            let b = transform.transform_point(vec2_to_f32(v).extend(0.));
+            assert!((a - b.truncate()).length() < 0.001);
+        }
+
+        let global =
+            GlobalTransform2::from(parent).propagate_transform(child, PropagateTransform2::ALL);
+        let product = parent.mul_transform(child);
+        assert!(global.is_mirrored());
+        assert!(product.is_mirrored());
+        for v in vs {
+            assert!((global.mul_vec2(v) - product.transform_point(v)).length() < e);
+            let w = product
+                .inverse()
+                .transform_point(product.transform_point(v));
+            assert!((w - v).length() < e);
+        }
+
+        let propagation = PropagateTransform2::TRANSLATION
+            | PropagateTransform2::DEPTH
+            | PropagateTransform2::ROTATION
+            | PropagateTransform2::SCALE;
+        let unflipped = GlobalTransform2::from(parent).propagate_transform(child, propagation);
+        assert!(!unflipped.is_mirrored());
+        assert!((unflipped.translation() - global.translation()).length() < e);
+
+        let computed = global.compute_transform();
+        assert!(computed.flip_x && !computed.flip_y);
+        for v in vs {
+            assert!((computed.transform_point(v) - global.mul_vec2(v)).length() < e);
+        }
+
+        // the flip of a `flip_y` parent isn't mistaken for a half turn
+        let parent_y = GlobalTransform2::from(Transform2::IDENTITY.with_flip_y(true));
+        let unflipped = parent_y
+            .propagate_transform(child, PropagateTransform2::ALL - PropagateTransform2::FLIP);
+        assert!(unflipped
+            .affine()
+            .matrix2
+            .abs_diff_eq(GlobalTransform2::from(child).affine().matrix2, e));
+        let flipped = parent_y.propagate_transform(child, PropagateTransform2::ALL);
+        let computed = flipped.compute_transform();
+        assert!(!computed.flip_x && computed.flip_y);
+        assert!((computed.rotation.as_radians() + 0.3).abs() < e);
+        for v in vs {
+            assert!((computed.transform_point(v) - flipped.mul_vec2(v)).length() < e);
+        }
+        let grandchild = flipped.propagate_transform(child, PropagateTransform2::ROTATION);
+        assert!((grandchild.rotation().as_radians()).abs() < e);
+        let local = flipped
+            .reparented_to(&parent_y, PropagateTransform2::ALL)
+            .unwrap();
+        assert!(!local.is_mirrored());
+        assert!((local.rotation.as_radians() - 0.3).abs() < e);
+        let local = flipped
+            .reparented_to(
+                &parent_y,
+                PropagateTransform2::ALL - PropagateTransform2::FLIP,
+            )
+            .unwrap();
+        assert!(local.flip_y && !local.flip_x);
+    }
+
+    #[test]
+    fn translation_axes() {
+        let e = 0.001;
+        let parent = GlobalTransform2::from(Transform2::from_xy(10., 20.).with_rotation(0.5));
+        let child = Transform2::from_xy(3., -4.);
+        let mapped = parent.mul_vec2(child.translation);
+        let x = parent.propagate_transform(child, PropagateTransform2::TRANSLATION_X);
+        assert!((x.translation() - vec2(mapped.x, -4.)).length() < e);
+        let y = parent.propagate_transform(child, PropagateTransform2::TRANSLATION_Y);
+        assert!((y.translation() - vec2(3., mapped.y)).length() < e);
+        let both = parent.propagate_transform(child, PropagateTransform2::TRANSLATION);
+        assert!((both.translation() - mapped).length() < e);
+
+        let scaled = GlobalTransform2::from(
+            Transform2::from_xy(10., 20.)
+                .with_rotation(0.5)
+                .with_scale(Transform2::IDENTITY.scale * 2.),
+        );
+        let offset = |propagation| {
+            scaled
+                .propagate_transform(child, PropagateTransform2::TRANSLATION | propagation)
+                .translation()
+                - scaled.translation()
+        };
+        let unrotated = offset(PropagateTransform2::TRANSLATION_UNROTATED);
+        assert!((unrotated - child.translation * 2.).length() < e);
+        let unscaled = offset(PropagateTransform2::TRANSLATION_UNSCALED);
+        assert!((unscaled - Rotation2::radians(0.5) * child.translation).length() < e);
+        let pure = offset(
+            PropagateTransform2::TRANSLATION_UNROTATED | PropagateTransform2::TRANSLATION_UNSCALED,
+        );
+        assert!((pure - child.translation).length() < e);
+    }
+
+    #[test]
+    fn weighted() {
+        let e = 0.001;
+        let parent = GlobalTransform2::from(
+            Transform2::from_xyz(10., -20., 4.)
+                .with_rotation(1.)
+                .with_scale(Transform2::IDENTITY.scale * 3.),
+        );
+        assert_eq!(parent.weighted(&PropagationWeights2::ONE), parent);
+
+        let zero = parent.weighted(&PropagationWeights2::splat(0.));
+        assert!(zero.affine().abs_diff_eq(RealAffine2::IDENTITY, e));
+        assert_eq!(zero.depth(), 0.);
+
+        let weights = PropagationWeights2 {
+            translation: vec2(0.3, 0.5),
+            rotation: 0.5,
+            scale: 0.5,
+            depth: 0.25,
+        };
+        let weighted = parent.weighted(&weights);
+        assert!((weighted.translation() - vec2(3., -10.)).length() < e);
+        assert!((weighted.rotation().as_radians() - 0.5).abs() < e);
+        assert!((weighted.compute_transform().scale_xy() - RealVec2::splat(2.)).length() < e);
+        assert!((weighted.depth() - 1.).abs() < e);
+
+        // the documented jump where the parent's angle wraps
+        let rotation_weight = PropagationWeights2 {
+            rotation: 0.5,
+            ..PropagationWeights2::ONE
+        };
+        let before = GlobalTransform2::from(Transform2::from_rotation(PI - 0.1));
+        let after = GlobalTransform2::from(Transform2::from_rotation(-PI + 0.1));
+        let before = before.weighted(&rotation_weight).rotation().as_radians();
+        let after = after.weighted(&rotation_weight).rotation().as_radians();
+        assert!((before - (PI - 0.1) / 2.).abs() < e);
+        assert!((after + (PI - 0.1) / 2.).abs() < e);
+    }
+
+    #[test]
+    fn lerp() {
+        let e = 0.001;
+        let a = Transform2::from_xyz(0., 10., 2.).with_rotation(PI - 0.1);
+        let b = Transform2::from_xyz(4., -10., 4.)
+            .with_rotation(-PI + 0.1 + 4. * TAU)
+            .with_scale(Transform2::IDENTITY.scale * 3.);
+        let c = a.lerp(b, 0.5);
+        assert!((c.translation - vec2(2., 0.)).length() < e);
+        assert!((c.depth - 3.).abs() < e);
+        assert!(c.rotation.angle_to(Rotation2::radians(PI)).abs() < e);
+        assert!((c.scale_xy() - RealVec2::splat(2.)).length() < e);
+
+        let g = GlobalTransform2::from(a).lerp(GlobalTransform2::from(b), 0.5);
+        assert!((g.translation() - c.translation).length() < e);
+        assert!((g.depth() - c.depth).abs() < e);
+        assert!(g.rotation().angle_to(Rotation2::radians(PI)).abs() < e);
+        assert!((g.compute_transform().scale_xy() - c.scale_xy()).length() < e);
+
+        let d = a.smooth_towards(b, 2., 0.);
+        assert!((d.translation - a.translation).length() < e);
+        assert!(d.rotation.angle_to(a.rotation).abs() < e);
+        let d = a.smooth_towards(b, 1000., 1.);
+        assert!((d.translation - b.translation).length() < e);
+    }
+
+    #[test]
+    fn look_at() {
+        let e = 0.001;
+        let mut transform2 = Transform2::from_xy(1., 1.);
+        transform2.look_at(vec2(1., 5.), RealVec2::X);
+        assert!((transform2.rotation.as_degrees() - 90.).abs() < e);
+        transform2.rotate_towards(vec2(-3., 1.), RealVec2::X, 0.25 * PI);
+        assert!((transform2.rotation.as_degrees() - 135.).abs() < e);
+        transform2.move_towards(vec2(4., 5.), 2.5);
+        assert!((transform2.translation - vec2(2.5, 3.)).length() < e);
+        transform2.move_towards(vec2(4., 5.), 10.);
+        assert_eq!(transform2.translation, vec2(4., 5.));
+
+        let parent = GlobalTransform2::from(
+            Transform2::from_xy(-4., 9.)
+                .with_rotation(2.)
+                .with_scale(Transform2::IDENTITY.scale * 3.),
+        );
+        let target = vec2(10., -3.);
+        for i in 0..=255 {
+            let propagation = PropagateTransform2::from_bits(i).unwrap();
+            let mut child = Transform2::from_xy(2., 5.);
+            child.look_at_world(&parent, propagation, target, RealVec2::Y);
+            let global = parent.propagate_transform(child, propagation);
+            let forward = global.affine().transform_vector2(RealVec2::Y);
+            let direction = target - global.translation();
+            assert!(forward.normalize().dot(direction.normalize()) > 1. - e);
+        }
+    }
+
     #[test]
     fn propagate() {
-        for i in 1..=15 {
-            assert!(!PropagateTransform2::NOTHING.inherits(PropagateTransform2(i)));
+        assert_eq!(
+            PropagateTransform2::from_bits(200).map(|p| p.bits()),
+            Some(200)
+        );
+        // every bit of the `u8` belongs to a named flag
+        assert!((0..=u8::MAX).all(|bits| PropagateTransform2::from_bits(bits).is_some()));
+        assert_eq!(
+            PropagateTransform2::from_bits_truncate(0xff),
+            PropagateTransform2::ALL
+                | PropagateTransform2::TRANSLATION_UNROTATED
+                | PropagateTransform2::TRANSLATION_UNSCALED
+        );
+
+        let p = PropagateTransform2::TRANSLATION | PropagateTransform2::SCALE;
+        assert_eq!(p.to_string(), "TRANSLATION | SCALE");
+        assert_eq!(format!("{p:?}"), "PropagateTransform2(TRANSLATION | SCALE)");
+        assert_eq!(PropagateTransform2::NOTHING.to_string(), "NOTHING");
+        assert_eq!(
+            (PropagateTransform2::ALL | PropagateTransform2::TRANSLATION_UNSCALED).to_string(),
+            "ALL | TRANSLATION_UNSCALED"
+        );
+        assert_eq!(
+            p.iter().collect::<Vec<_>>(),
+            [PropagateTransform2::TRANSLATION, PropagateTransform2::SCALE]
+        );
+        assert_eq!(
+            (PropagateTransform2::TRANSLATION_Y | PropagateTransform2::DEPTH).to_string(),
+            "TRANSLATION_Y | DEPTH"
+        );
+
+        assert!(p.contains(PropagateTransform2::TRANSLATION_X));
+        assert!(!p.contains(PropagateTransform2::TRANSLATION | PropagateTransform2::DEPTH));
+        assert!(p.intersects(PropagateTransform2::TRANSLATION | PropagateTransform2::DEPTH));
+        assert!(PropagateTransform2::NOTHING.is_empty());
+        assert!(PropagateTransform2::default().is_all());
+        assert_eq!(
+            PropagateTransform2::ALL - p,
+            PropagateTransform2::DEPTH | PropagateTransform2::ROTATION | PropagateTransform2::FLIP
+        );
+        assert_eq!(!PropagateTransform2::NOTHING, PropagateTransform2::ALL);
+        assert_eq!(
+            !PropagateTransform2::ROTATION,
+            PropagateTransform2::ALL - PropagateTransform2::ROTATION
+        );
+        assert_eq!(
+            !(PropagateTransform2::ROTATION | PropagateTransform2::TRANSLATION_UNROTATED),
+            PropagateTransform2::ALL - PropagateTransform2::ROTATION
+        );
+        let parent = GlobalTransform2::from(
+            Transform2::from_xy(10., 0.)
+                .with_rotation(Rotation2::degrees(90.))
+                .with_scale(Transform2::IDENTITY.scale * 2.),
+        );
+        let child =
+            parent.propagate_transform(Transform2::from_xy(1., 0.), !PropagateTransform2::ROTATION);
+        assert!((child.translation() - RealVec2::new(10., 2.)).length() < 0.001);
+        assert_eq!(p ^ p, PropagateTransform2::NOTHING);
+        let mut q = p;
+        q.set(PropagateTransform2::SCALE, false);
+        q.toggle(PropagateTransform2::DEPTH);
+        assert_eq!(
+            q,
+            PropagateTransform2::TRANSLATION | PropagateTransform2::DEPTH
+        );
+
+        for i in 1..=63 {
+            assert!(
+                !PropagateTransform2::NOTHING.inherits(PropagateTransform2::from_bits(i).unwrap())
+            );
         }
 
-        for i in 1..=15 {
-            assert!(PropagateTransform2::ALL.inherits(PropagateTransform2(i)));
+        for i in 1..=63 {
+            assert!(PropagateTransform2::ALL.inherits(PropagateTransform2::from_bits(i).unwrap()));
         }
 
-        for i in 1..=15 {
-            let p = PropagateTransform2(i);
+        for i in 1..=63 {
+            let p = PropagateTransform2::from_bits(i).unwrap();
             assert!(p.inherits(p));
-            for j in 1..=15 {
-                let q = PropagateTransform2(j & (!i));
+            for j in 1..=63 {
+                let q = PropagateTransform2::from_bits(j & (!i)).unwrap();
                 assert!(!p.inherits(q));
             }
         }